@@ -0,0 +1,71 @@
+use crate::result::OptimizationResult;
+use crate::{Evaluator, MOEADOptimizer, NSGAOptimizer, SPEA2Optimizer, Solution};
+
+/// An optimization algorithm working with the problem traits.
+///
+/// Every optimizer implements it, so the algorithm can be picked at runtime,
+/// e.g. to benchmark several of them on the same problem:
+/// ```ignore
+/// let mut algorithms: Vec<Box<dyn Algorithm<_>>> = vec![
+///     Box::new(NSGAOptimizer::new(meta())),
+///     Box::new(SPEA2Optimizer::new(meta())),
+///     Box::new(MOEADOptimizer::new(meta())),
+/// ];
+///
+/// for algorithm in algorithms.iter_mut() {
+///     let result = algorithm.optimize(Box::new(DefaultEvaluator::new(100)));
+///     println!("{}: {} solutions", algorithm.name(), result.solutions.len());
+/// }
+/// ```
+pub trait Algorithm<S: Solution> {
+    /// A short name of the algorithm
+    fn name(&self) -> &'static str;
+
+    /// Run an optimization process using `eval` to determine termination condition
+    fn optimize(&mut self, eval: Box<dyn Evaluator>) -> OptimizationResult<S>;
+
+    /// Return the best solutions found so far
+    fn solutions(&self) -> Box<dyn Iterator<Item = &S> + '_>;
+}
+
+impl<S: Solution> Algorithm<S> for NSGAOptimizer<'_, S> {
+    fn name(&self) -> &'static str {
+        "NSGA-II"
+    }
+
+    fn optimize(&mut self, eval: Box<dyn Evaluator>) -> OptimizationResult<S> {
+        NSGAOptimizer::optimize(self, eval)
+    }
+
+    fn solutions(&self) -> Box<dyn Iterator<Item = &S> + '_> {
+        Box::new(NSGAOptimizer::solutions(self))
+    }
+}
+
+impl<S: Solution> Algorithm<S> for SPEA2Optimizer<'_, S> {
+    fn name(&self) -> &'static str {
+        "SPEA2"
+    }
+
+    fn optimize(&mut self, eval: Box<dyn Evaluator>) -> OptimizationResult<S> {
+        SPEA2Optimizer::optimize(self, eval)
+    }
+
+    fn solutions(&self) -> Box<dyn Iterator<Item = &S> + '_> {
+        Box::new(SPEA2Optimizer::solutions(self))
+    }
+}
+
+impl<S: Solution> Algorithm<S> for MOEADOptimizer<'_, S> {
+    fn name(&self) -> &'static str {
+        "MOEA/D"
+    }
+
+    fn optimize(&mut self, eval: Box<dyn Evaluator>) -> OptimizationResult<S> {
+        MOEADOptimizer::optimize(self, eval)
+    }
+
+    fn solutions(&self) -> Box<dyn Iterator<Item = &S> + '_> {
+        Box::new(MOEADOptimizer::solutions(self))
+    }
+}
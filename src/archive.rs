@@ -0,0 +1,233 @@
+use std::cmp::Ordering;
+
+use crate::result::ParetoSolution;
+use crate::sorting::{constrained_dominates, crowding_distance, dominates};
+
+/// An external archive of mutually non-dominated solutions.
+///
+/// Solutions are compared using constraint-domination: a feasible solution
+/// always wins over an infeasible one and the smaller total violation wins
+/// among infeasible solutions. Exact duplicates are never stored.
+///
+/// The optimizer keeps the best solutions it has found in an archive
+/// (see [`crate::NSGAOptimizer::archive()`]), but it can be used on its own as well:
+/// ```
+/// use nsga::ParetoArchive;
+///
+/// let mut archive = ParetoArchive::new();
+///
+/// assert!(archive.insert("a", vec![1., 2.]));
+/// assert!(archive.insert("b", vec![2., 1.]));
+/// assert!(!archive.insert("c", vec![2., 3.]));
+/// assert!(archive.insert("d", vec![0., 0.]));
+///
+/// assert_eq!(archive.len(), 1);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParetoArchive<S> {
+    entries: Vec<ParetoSolution<S>>,
+    epsilon: Option<Vec<f64>>,
+    capacity: Option<usize>,
+}
+
+impl<S> Default for ParetoArchive<S> {
+    fn default() -> Self {
+        ParetoArchive::new()
+    }
+}
+
+impl<S> ParetoArchive<S> {
+    /// Create an empty unbounded archive
+    pub fn new() -> Self {
+        ParetoArchive {
+            entries: Vec::new(),
+            epsilon: None,
+            capacity: None,
+        }
+    }
+
+    /// Use [epsilon-dominance](https://doi.org/10.1162/106365602760234108)
+    /// instead of the plain Pareto dominance.
+    ///
+    /// The objective space is divided into boxes of `epsilon` size, either one value
+    /// per objective or a single value for all of them. Only one solution
+    /// is kept per box, so the archive stays well spread and its size is bounded
+    /// by the objective ranges.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `epsilon` is empty or any of its values isn't positive.
+    pub fn epsilon(mut self, epsilon: Vec<f64>) -> Self {
+        assert!(
+            !epsilon.is_empty() && epsilon.iter().all(|e| *e > 0.),
+            "epsilon values must be positive"
+        );
+
+        self.epsilon = Some(epsilon);
+        self
+    }
+
+    /// Keep at most `capacity` solutions.
+    ///
+    /// When the archive is full, the most crowded solutions are removed first,
+    /// the boundary ones are always kept.
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = Some(capacity.max(1));
+        self
+    }
+
+    /// Insert a feasible solution with the given objective values.
+    ///
+    /// Returns true if the solution was added to the archive,
+    /// i.e. it's not dominated by or equal to any of the stored solutions.
+    /// Every stored solution dominated by the new one is removed.
+    pub fn insert(&mut self, solution: S, values: Vec<f64>) -> bool {
+        self.insert_with_violation(solution, values, 0.)
+    }
+
+    /// Insert a solution with the given objective values
+    /// and total constraint violation, 0 for feasible solutions.
+    ///
+    /// See [`ParetoArchive::insert()`].
+    pub fn insert_with_violation(&mut self, solution: S, values: Vec<f64>, violation: f64) -> bool {
+        let violation = if violation > 0. { violation } else { 0. };
+        let eps = self.epsilon.as_deref();
+        let key = key_of(eps, &values);
+
+        let mut replaced = None;
+
+        for (i, e) in self.entries.iter().enumerate() {
+            let e_key = key_of(eps, &e.values);
+
+            if e_key == key && e.violation == violation {
+                // Within the same box the solution closest to its corner wins
+                if eps.is_none()
+                    || !(dominates(&values, &e.values)
+                        || (!dominates(&e.values, &values)
+                            && corner_distance(eps, &values) < corner_distance(eps, &e.values)))
+                {
+                    return false;
+                }
+
+                replaced = Some(i);
+                break;
+            }
+
+            if constrained_dominates(&e_key, e.violation, &key, violation) {
+                return false;
+            }
+        }
+
+        if let Some(i) = replaced {
+            self.entries.swap_remove(i);
+        }
+
+        self.entries.retain(|e| {
+            !constrained_dominates(&key, violation, &key_of(eps, &e.values), e.violation)
+        });
+
+        self.entries.push(ParetoSolution {
+            solution,
+            values,
+            front: 0,
+            violation,
+        });
+
+        match self.capacity {
+            Some(capacity) if self.entries.len() > capacity => self.prune(capacity),
+            _ => true,
+        }
+    }
+
+    /// The number of stored solutions
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if there are no stored solutions
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterate over the stored solutions, in no particular order
+    pub fn iter(&self) -> std::slice::Iter<'_, ParetoSolution<S>> {
+        self.entries.iter()
+    }
+
+    /// Remove every stored solution, keeping the archive settings
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    // Remove the most crowded entries until the capacity is met,
+    // returns false if the last inserted entry was removed
+    fn prune(&mut self, capacity: usize) -> bool {
+        let mut last = self.entries.len() - 1;
+        let mut kept = true;
+
+        while self.entries.len() > capacity {
+            let distances =
+                crowding_distance(&self.entries.iter().map(|e| &e.values).collect::<Vec<_>>());
+
+            let (i, _) = distances
+                .iter()
+                .enumerate()
+                .rev()
+                .min_by(|a, b| a.1.partial_cmp(b.1).unwrap_or(Ordering::Equal))
+                .unwrap();
+
+            match i.cmp(&last) {
+                Ordering::Equal => kept = false,
+                Ordering::Less => last -= 1,
+                Ordering::Greater => {}
+            }
+
+            self.entries.remove(i);
+        }
+
+        kept
+    }
+}
+
+// Values used for the dominance comparison: box indices with epsilon-dominance
+fn key_of(epsilon: Option<&[f64]>, values: &[f64]) -> Vec<f64> {
+    match epsilon {
+        None => values.to_vec(),
+        Some(eps) => values
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (v / eps[i.min(eps.len() - 1)]).floor())
+            .collect(),
+    }
+}
+
+// Squared distance from the corner of the box the values belong to
+fn corner_distance(epsilon: Option<&[f64]>, values: &[f64]) -> f64 {
+    let eps = epsilon.unwrap_or(&[1.]);
+
+    values
+        .iter()
+        .zip(key_of(epsilon, values))
+        .enumerate()
+        .map(|(i, (v, b))| (v - b * eps[i.min(eps.len() - 1)]).powi(2))
+        .sum()
+}
+
+impl<S> IntoIterator for ParetoArchive<S> {
+    type Item = ParetoSolution<S>;
+    type IntoIter = std::vec::IntoIter<ParetoSolution<S>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+impl<'r, S> IntoIterator for &'r ParetoArchive<S> {
+    type Item = &'r ParetoSolution<S>;
+    type IntoIter = std::slice::Iter<'r, ParetoSolution<S>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.iter()
+    }
+}
@@ -1,8 +1,9 @@
 use crate::*;
-use rand::{thread_rng, Rng};
+use rand::{Rng, RngCore};
 use std::ops::Range;
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CandidateF64 {
     pub val: f64,
     pub range_start: f64,
@@ -13,9 +14,9 @@ impl Solution for CandidateF64 {
     // SBX Crossover
     // https://youtu.be/7-NPqSvutr0?t=718
     // https://github.com/baopng/NSGA-II/blob/master/nsga2/utils.py#L89
-    fn crossover(&mut self, other: &mut Self) {
+    fn crossover(&mut self, other: &mut Self, rng: &mut dyn RngCore) {
         let crossover_param: f64 = 2.;
-        let u: f64 = thread_rng().gen_range(0.0..1.0);
+        let u: f64 = rng.gen_range(0.0..1.0);
 
         let beta = if u <= 0.5 {
             (2. * u).powf(1. / (crossover_param + 1.))
@@ -33,16 +34,16 @@ impl Solution for CandidateF64 {
     // Polynomial mutation
     // https://youtu.be/7-NPqSvutr0?t=916
     // https://github.com/baopng/NSGA-II/blob/master/nsga2/utils.py#L108
-    fn mutate(&mut self) {
+    fn mutate(&mut self, rng: &mut dyn RngCore) {
         let mutation_param: f64 = 5.;
-        let u: f64 = thread_rng().gen_range(0.0..1.0);
+        let u: f64 = rng.gen_range(0.0..1.0);
         let r0: f64 = self.range_start;
         let r1: f64 = self.range_end;
 
         let delta = if u < 0.5 {
             (2. * u).powf(1. / (mutation_param + 1.)) - 1.
         } else {
-            1. - (2. * (1. - u)).powf(-1. / (mutation_param + 1.))
+            1. - (2. * (1. - u)).powf(1. / (mutation_param + 1.))
         };
 
         if u < 0.5 {
@@ -81,9 +82,9 @@ impl<'a> Meta<'a, CandidateF64> for ParamsF64<'a> {
         self.mutation_odds
     }
 
-    fn random_solution(&mut self) -> CandidateF64 {
+    fn random_solution(&mut self, rng: &mut dyn RngCore) -> CandidateF64 {
         CandidateF64 {
-            val: thread_rng().gen_range(self.val_range.clone()),
+            val: rng.gen_range(self.val_range.clone()),
             range_start: self.val_range.start,
             range_end: self.val_range.end,
         }
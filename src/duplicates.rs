@@ -0,0 +1,56 @@
+use std::collections::HashSet;
+
+type EqualFn<'a, S> = dyn Fn(&S, &S) -> bool + 'a;
+type HashFn<'a, S> = dyn Fn(&S) -> u64 + 'a;
+
+/// A way to detect duplicate solutions, see [`crate::NSGAOptimizer::duplicates()`]
+///
+/// Duplicates are replaced with mutated copies before the survival selection,
+/// falling back to random solutions, so that the population can't collapse
+/// into copies of a single solution. Random solutions still duplicating
+/// others are dropped, so the population gets smaller when the search space
+/// or the generator doesn't have enough distinct solutions.
+pub enum Duplicates<'a, S> {
+    /// Solutions are duplicates if the function returns true for them
+    Equal(Box<EqualFn<'a, S>>),
+
+    /// Solutions are duplicates if the function returns the same hash for them.
+    ///
+    /// This is faster than [`Duplicates::Equal`] for large populations.
+    Hash(Box<HashFn<'a, S>>),
+
+    /// Solutions are duplicates if all their objective values differ
+    /// by no more than `tolerance`.
+    ///
+    /// Replacements have to be evaluated first, so this is only applied
+    /// by [`crate::NSGAOptimizer::optimize()`], not with `ask` and `tell`.
+    Objectives { tolerance: f64 },
+}
+
+impl<S> Duplicates<'_, S> {
+    // Returns true if `sol` duplicates any of `others`,
+    // hashes of which are in `hashes` for `Duplicates::Hash`
+    pub(crate) fn contains<'s>(
+        &self,
+        sol: &S,
+        others: impl Iterator<Item = &'s S>,
+        hashes: &HashSet<u64>,
+    ) -> bool
+    where
+        S: 's,
+    {
+        match self {
+            Duplicates::Equal(eq) => others.into_iter().any(|o| eq(sol, o)),
+            Duplicates::Hash(hash) => hashes.contains(&hash(sol)),
+            Duplicates::Objectives { .. } => false,
+        }
+    }
+}
+
+// Returns true if all the values differ by no more than `tolerance`
+pub(crate) fn close(vals1: &[f64], vals2: &[f64], tolerance: f64) -> bool {
+    vals1
+        .iter()
+        .zip(vals2)
+        .all(|(v1, v2)| (v1 - v2).abs() <= tolerance)
+}
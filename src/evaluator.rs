@@ -1,18 +1,103 @@
+use std::time::Duration;
+
+use crate::archive::ParetoArchive;
+use crate::hypervolume::hypervolume;
+use crate::optimizer::oriented;
+use crate::sorting::{constrained_dominates, dominates, non_dominated_sort};
+use crate::Direction;
+
+/// The state of an optimization, passed to evaluators once per generation.
+///
+/// Objective values are as returned by the objectives,
+/// see [`crate::Objective::direction()`].
+#[derive(Debug, Clone, Default)]
+pub struct Progress {
+    /// The current generation, 0 for the initial population
+    pub generation: usize,
+    /// The number of objective evaluations so far
+    pub evaluations: usize,
+    /// Time since the optimization started
+    pub elapsed: Duration,
+    /// Objective values of every population member
+    pub population: Vec<Vec<f64>>,
+    /// Total constraint violation of every population member, 0 for feasible ones
+    pub violations: Vec<f64>,
+    /// Objective values of the feasible non-dominated population members
+    pub front: Vec<Vec<f64>>,
+    /// Directions of the objectives
+    pub directions: Vec<Direction>,
+}
+
+impl Progress {
+    // `members` are the objective values oriented for minimization
+    // along with the total constraint violation
+    pub(crate) fn new<'a>(
+        directions: Vec<Direction>,
+        generation: usize,
+        evaluations: usize,
+        elapsed: Duration,
+        members: impl Iterator<Item = (&'a [f64], f64)>,
+    ) -> Self {
+        let (values, violations): (Vec<_>, Vec<_>) = members.unzip();
+
+        let feasible: Vec<_> = values
+            .iter()
+            .zip(&violations)
+            .filter_map(|(v, violation)| (*violation <= 0.).then_some(*v))
+            .collect();
+
+        let front = non_dominated_sort(&feasible)
+            .into_iter()
+            .zip(&feasible)
+            .filter(|(front, _)| *front == 0)
+            .map(|(_, v)| oriented(&directions, v))
+            .collect();
+
+        Progress {
+            generation,
+            evaluations,
+            elapsed,
+            population: values.iter().map(|v| oriented(&directions, v)).collect(),
+            violations,
+            front,
+            directions,
+        }
+    }
+
+    // The front oriented for minimization
+    fn minimized_front(&self) -> Vec<Vec<f64>> {
+        self.front
+            .iter()
+            .map(|v| oriented(&self.directions, v))
+            .collect()
+    }
+}
+
 /// Evaluate the termination condition
 pub trait Evaluator {
-    /// Returns true if an optimization process can be stopped
-    fn can_terminate(&mut self, iter: usize, values: Vec<f64>) -> bool;
+    /// Returns true if an optimization process can be stopped,
+    /// called once per generation
+    fn can_terminate(&mut self, progress: &Progress) -> bool;
 }
 
 /// Implements a default termination condition.
 ///
-/// It saves best solutions on every iteration
-/// and returns when there was no improvement for the provided
-/// `terminate_early` iterations.
+/// It keeps every non-dominated solution of the populations seen so far,
+/// using constraint-domination, and returns when no new solution dominated
+/// any of them for the provided `terminate_early` generations.
+///
+/// Solutions merely filling the gaps between the kept ones don't count,
+/// neither do improvements smaller than the tolerance times the range
+/// of the kept solutions in every objective, otherwise fronts converging
+/// asymptotically would improve forever.
+///
+/// At most as many solutions as the population holds are kept, the most
+/// crowded ones are dropped first, so every generation takes the same time
+/// no matter how long the optimization runs.
 pub struct DefaultEvaluator {
     terminate_early: usize,
-    iter: usize,
-    values: Option<Vec<f64>>,
+    tolerance: f64,
+    best: ParetoArchive<()>,
     no_improvements: usize,
 }
 
@@ -20,47 +105,266 @@ impl DefaultEvaluator {
     pub fn new(terminate_early: usize) -> Self {
         DefaultEvaluator {
             terminate_early,
-            iter: 0,
-            values: None,
+            tolerance: 1e-2,
+            best: ParetoArchive::new(),
             no_improvements: 0,
         }
     }
-}
 
-impl Evaluator for DefaultEvaluator {
-    fn can_terminate(&mut self, iter: usize, values: Vec<f64>) -> bool {
-        match &self.values {
-            None => {
-                self.values = Some(values);
-                self.iter = iter;
+    /// Set the relative tolerance, 1e-2 by default
+    pub fn tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
 
-                false
+    // Returns true if `values` dominate any of the kept solutions by more than the tolerance
+    fn improves(&self, values: &[f64], violation: f64, ranges: &[f64]) -> bool {
+        self.best.iter().any(|kept| {
+            if violation > 0. || kept.violation > 0. {
+                return constrained_dominates(values, violation, &kept.values, kept.violation);
             }
-            Some(old_best) => {
-                let has_better = old_best
-                    .iter()
-                    .zip(values.iter())
-                    .any(|(old, new)| new < old);
 
-                let no_worse = old_best
+            dominates(values, &kept.values)
+                && values
                     .iter()
-                    .zip(values.iter())
-                    .all(|(old, new)| new <= old);
+                    .zip(&kept.values)
+                    .zip(ranges)
+                    .any(|((v, k), range)| k - v > self.tolerance * range)
+        })
+    }
 
-                if has_better && no_worse {
-                    self.values = Some(values);
-                    self.iter = iter;
-                    self.no_improvements = 0;
+    // The range of the feasible kept solutions in every objective, 1 if there's none
+    fn ranges(&self) -> Vec<f64> {
+        let objectives = self.best.iter().next().map_or(0, |s| s.values.len());
 
-                    false
-                } else if iter == self.iter {
-                    false
+        (0..objectives)
+            .map(|i| {
+                let (min, max) = self
+                    .best
+                    .iter()
+                    .filter(|s| s.is_feasible())
+                    .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), s| {
+                        (min.min(s.values[i]), max.max(s.values[i]))
+                    });
+
+                if max > min {
+                    max - min
                 } else {
-                    self.iter = iter;
-                    self.no_improvements += 1;
-                    self.no_improvements >= self.terminate_early
+                    1.
                 }
+            })
+            .collect()
+    }
+}
+
+impl Evaluator for DefaultEvaluator {
+    fn can_terminate(&mut self, progress: &Progress) -> bool {
+        let mut improved = self.best.is_empty();
+        if improved {
+            self.best = ParetoArchive::new().capacity(progress.population.len());
+        }
+
+        let ranges = self.ranges();
+
+        for (values, violation) in progress.population.iter().zip(&progress.violations) {
+            let values = oriented(&progress.directions, values);
+
+            improved = improved || self.improves(&values, *violation, &ranges);
+            self.best.insert_with_violation((), values, *violation);
+        }
+
+        if improved {
+            self.no_improvements = 0;
+        } else {
+            self.no_improvements += 1;
+        }
+
+        self.no_improvements >= self.terminate_early
+    }
+}
+
+/// Stops after the given number of generations
+pub struct MaxGenerations {
+    generations: usize,
+}
+
+impl MaxGenerations {
+    pub fn new(generations: usize) -> Self {
+        MaxGenerations { generations }
+    }
+}
+
+impl Evaluator for MaxGenerations {
+    fn can_terminate(&mut self, progress: &Progress) -> bool {
+        progress.generation >= self.generations
+    }
+}
+
+/// Stops once the objectives were evaluated the given number of times.
+///
+/// The check happens between generations, so a few more evaluations
+/// than that may be made.
+pub struct MaxEvaluations {
+    max: usize,
+}
+
+impl MaxEvaluations {
+    pub fn new(max: usize) -> Self {
+        MaxEvaluations { max }
+    }
+}
+
+impl Evaluator for MaxEvaluations {
+    fn can_terminate(&mut self, progress: &Progress) -> bool {
+        progress.evaluations >= self.max
+    }
+}
+
+/// Stops once the given wall-clock time has passed since the optimization started
+pub struct TimeBudget {
+    budget: Duration,
+}
+
+impl TimeBudget {
+    pub fn new(budget: Duration) -> Self {
+        TimeBudget { budget }
+    }
+}
+
+impl Evaluator for TimeBudget {
+    fn can_terminate(&mut self, progress: &Progress) -> bool {
+        progress.elapsed >= self.budget
+    }
+}
+
+/// Stops once a feasible solution reaches the target value in every objective,
+/// maximized objectives reach their targets from below
+pub struct TargetValues {
+    targets: Vec<f64>,
+}
+
+impl TargetValues {
+    pub fn new(targets: Vec<f64>) -> Self {
+        TargetValues { targets }
+    }
+}
+
+impl Evaluator for TargetValues {
+    fn can_terminate(&mut self, progress: &Progress) -> bool {
+        let targets = oriented(&progress.directions, &self.targets);
+
+        progress
+            .minimized_front()
+            .iter()
+            .any(|values| values.iter().zip(&targets).all(|(v, t)| v <= t))
+    }
+}
+
+/// Stops when the hypervolume of the front bounded by `reference`
+/// hasn't improved by more than the tolerance for the given number of generations.
+///
+/// The reference point is the lower bound of maximized objectives,
+/// see [`crate::hypervolume`].
+pub struct HypervolumeStagnation {
+    reference: Vec<f64>,
+    generations: usize,
+    tolerance: f64,
+    best: Option<f64>,
+    no_improvements: usize,
+}
+
+impl HypervolumeStagnation {
+    pub fn new(reference: Vec<f64>, generations: usize) -> Self {
+        HypervolumeStagnation {
+            reference,
+            generations,
+            tolerance: 0.,
+            best: None,
+            no_improvements: 0,
+        }
+    }
+
+    /// Set the smallest hypervolume increase counting as an improvement, 0 by default
+    pub fn tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+}
+
+impl Evaluator for HypervolumeStagnation {
+    fn can_terminate(&mut self, progress: &Progress) -> bool {
+        let reference = oriented(&progress.directions, &self.reference);
+        let volume = hypervolume(&progress.minimized_front(), &reference);
+
+        match self.best {
+            Some(best) if volume <= best + self.tolerance => self.no_improvements += 1,
+            _ => {
+                self.best = Some(volume);
+                self.no_improvements = 0;
             }
         }
+
+        self.no_improvements >= self.generations
+    }
+}
+
+/// Stops as soon as any of the evaluators says so.
+///
+/// Every one of them is asked, so all of them keep track of the optimization:
+/// ```
+/// use nsga::{Any, MaxGenerations, TimeBudget};
+/// use std::time::Duration;
+///
+/// let eval = Any::new(vec![
+///     Box::new(TimeBudget::new(Duration::from_secs(30 * 60))),
+///     Box::new(MaxGenerations::new(500)),
+/// ]);
+/// ```
+pub struct Any {
+    evaluators: Vec<Box<dyn Evaluator>>,
+}
+
+impl Any {
+    pub fn new(evaluators: Vec<Box<dyn Evaluator>>) -> Self {
+        Any { evaluators }
+    }
+}
+
+impl Evaluator for Any {
+    fn can_terminate(&mut self, progress: &Progress) -> bool {
+        answers(&mut self.evaluators, progress)
+            .into_iter()
+            .any(|stop| stop)
     }
 }
+
+/// Stops once all of the evaluators say so at the same time.
+///
+/// Every one of them is asked, so all of them keep track of the optimization.
+/// With no evaluators it never stops.
+pub struct All {
+    evaluators: Vec<Box<dyn Evaluator>>,
+}
+
+impl All {
+    pub fn new(evaluators: Vec<Box<dyn Evaluator>>) -> Self {
+        All { evaluators }
+    }
+}
+
+impl Evaluator for All {
+    fn can_terminate(&mut self, progress: &Progress) -> bool {
+        !self.evaluators.is_empty()
+            && answers(&mut self.evaluators, progress)
+                .into_iter()
+                .all(|stop| stop)
+    }
+}
+
+// Ask every evaluator, not stopping at the first answer
+fn answers(evaluators: &mut [Box<dyn Evaluator>], progress: &Progress) -> Vec<bool> {
+    evaluators
+        .iter_mut()
+        .map(|e| e.can_terminate(progress))
+        .collect()
+}
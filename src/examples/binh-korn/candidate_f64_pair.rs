@@ -1,5 +1,5 @@
 use nsga::{CandidateF64, Constraint, Meta, Objective, Ratio, Solution};
-use rand::{thread_rng, Rng};
+use rand::{Rng, RngCore};
 use std::ops::RangeInclusive;
 
 #[derive(Clone, Debug)]
@@ -9,14 +9,14 @@ pub struct CandidateF64Pair {
 }
 
 impl Solution for CandidateF64Pair {
-    fn crossover(&mut self, other: &mut Self) {
-        self.x.crossover(&mut other.x);
-        self.y.crossover(&mut other.y);
+    fn crossover(&mut self, other: &mut Self, rng: &mut dyn RngCore) {
+        self.x.crossover(&mut other.x, rng);
+        self.y.crossover(&mut other.y, rng);
     }
 
-    fn mutate(&mut self) {
-        self.x.mutate();
-        self.y.mutate();
+    fn mutate(&mut self, rng: &mut dyn RngCore) {
+        self.x.mutate(rng);
+        self.y.mutate(rng);
     }
 }
 
@@ -43,15 +43,15 @@ impl<'a> Meta<'a, CandidateF64Pair> for ParamsF64Pair<'a> {
         self.mutation_odds
     }
 
-    fn random_solution(&mut self) -> CandidateF64Pair {
+    fn random_solution(&mut self, rng: &mut dyn RngCore) -> CandidateF64Pair {
         let x = CandidateF64 {
-            val: thread_rng().gen_range(self.val_range_x.clone()),
+            val: rng.gen_range(self.val_range_x.clone()),
             range_start: *self.val_range_x.start(),
             range_end: *self.val_range_x.end(),
         };
 
         let y = CandidateF64 {
-            val: thread_rng().gen_range(self.val_range_y.clone()),
+            val: rng.gen_range(self.val_range_y.clone()),
             range_start: *self.val_range_x.start(),
             range_end: *self.val_range_x.end(),
         };
@@ -20,26 +20,17 @@ impl Objective<CandidateF64Pair> for Objective2 {
 
 pub struct Constraint1 {}
 impl Constraint<CandidateF64Pair> for Constraint1 {
-    fn value(&self, candidate: &CandidateF64Pair, val: f64) -> f64 {
-        let cval = (candidate.x.val - 5.).powi(2) + candidate.y.val.powi(2);
-        if cval > 25. {
-            f64::MAX
-        } else {
-            val
-        }
+    // (x - 5)^2 + y^2 <= 25
+    fn violation(&self, candidate: &CandidateF64Pair) -> f64 {
+        (candidate.x.val - 5.).powi(2) + candidate.y.val.powi(2) - 25.
     }
 }
 
 pub struct Constraint2 {}
 impl Constraint<CandidateF64Pair> for Constraint2 {
-    fn value(&self, candidate: &CandidateF64Pair, val: f64) -> f64 {
-        let cval = (candidate.x.val - 8.).powi(2) + (candidate.y.val + 3.).powi(2);
-
-        if cval < 7.7 {
-            f64::MAX
-        } else {
-            val
-        }
+    // (x - 8)^2 + (y + 3)^2 >= 7.7
+    fn violation(&self, candidate: &CandidateF64Pair) -> f64 {
+        7.7 - (candidate.x.val - 8.).powi(2) - (candidate.y.val + 3.).powi(2)
     }
 }
 
@@ -81,14 +72,12 @@ fn main() {
     let mut optimizer = NSGAOptimizer::new(meta);
     optimizer
         .optimize(Box::new(DefaultEvaluator::new(100)))
+        .into_iter()
         .take(samples)
         .for_each(|s| {
-            let x = (4. * s.x.val.powi(2)) + (4. * s.y.val.powi(2));
-            let y = (s.x.val - 5.).powi(2) + (s.y.val - 5.).powi(2);
-
             println!(
                 "{0: <10.4} | {1: <10.4} | {2: <10.4} | {3: <10.4}",
-                s.x.val, s.y.val, x, y
+                s.solution.x.val, s.solution.y.val, s.values[0], s.values[1]
             );
         });
 }
@@ -0,0 +1,176 @@
+//! The [hypervolume](https://doi.org/10.1109/4235.797969) indicator:
+//! the volume of the objective space dominated by a set of solutions
+//! and bounded by a reference point.
+//!
+//! The larger the hypervolume, the closer the set is to the Pareto front
+//! and the better it covers it, so it can be used to compare optimization runs
+//! or to track their convergence:
+//! ```
+//! use nsga::hypervolume::hypervolume;
+//!
+//! let front = vec![vec![1., 3.], vec![2., 2.], vec![3., 1.]];
+//!
+//! assert_eq!(hypervolume(&front, &[4., 4.]), 6.);
+//! ```
+//!
+//! Objective values are minimized, points not strictly better than
+//! the reference point in every objective don't contribute to the volume.
+
+use rand::{Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::cmp::Ordering;
+
+use crate::sorting::dominates;
+
+/// The number of samples [`hypervolume()`] uses to estimate the volume
+/// beyond three objectives
+pub const SAMPLES: usize = 100_000;
+
+/// Compute the hypervolume of `points` bounded by `reference`.
+///
+/// It's exact for up to three objectives, computed with the
+/// [WFG algorithm](https://doi.org/10.1109/TEVC.2010.2077298).
+/// For more objectives the exact computation gets too expensive,
+/// so it's estimated with [`SAMPLES`] seeded Monte Carlo samples,
+/// see [`estimate()`].
+///
+/// # Panics
+///
+/// Panics if any of the points has a different number of objectives than `reference`.
+pub fn hypervolume<V: AsRef<[f64]>>(points: &[V], reference: &[f64]) -> f64 {
+    if reference.len() > 3 {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        return estimate(points, reference, SAMPLES, &mut rng);
+    }
+
+    wfg(relevant(points, reference), reference)
+}
+
+/// Estimate the hypervolume of `points` bounded by `reference` using `samples`
+/// uniformly distributed random points.
+///
+/// The samples are drawn from the box between the best values of `points`
+/// and `reference`, the error decreases with the square root of their number.
+///
+/// # Panics
+///
+/// Panics if any of the points has a different number of objectives than `reference`.
+pub fn estimate<V: AsRef<[f64]>>(
+    points: &[V],
+    reference: &[f64],
+    samples: usize,
+    rng: &mut dyn RngCore,
+) -> f64 {
+    let points = relevant(points, reference);
+    if points.is_empty() || samples == 0 {
+        return 0.;
+    }
+
+    let lower: Vec<f64> = (0..reference.len())
+        .map(|i| points.iter().map(|p| p[i]).fold(f64::INFINITY, f64::min))
+        .collect();
+
+    let volume: f64 = lower.iter().zip(reference).map(|(l, r)| r - l).product();
+
+    let mut sample = vec![0.; reference.len()];
+    let mut hits = 0;
+
+    for _ in 0..samples {
+        for (i, s) in sample.iter_mut().enumerate() {
+            *s = rng.gen_range(lower[i]..reference[i]);
+        }
+
+        if points
+            .iter()
+            .any(|p| p.iter().zip(&sample).all(|(v, s)| v <= s))
+        {
+            hits += 1;
+        }
+    }
+
+    volume * hits as f64 / samples as f64
+}
+
+// Non-dominated points strictly better than the reference point in every objective
+fn relevant<V: AsRef<[f64]>>(points: &[V], reference: &[f64]) -> Vec<Vec<f64>> {
+    let points: Vec<Vec<f64>> = points
+        .iter()
+        .map(|p| {
+            assert_eq!(
+                p.as_ref().len(),
+                reference.len(),
+                "points and the reference point must have the same number of objectives"
+            );
+
+            p.as_ref().to_vec()
+        })
+        .filter(|p| p.iter().zip(reference).all(|(v, r)| v < r))
+        .collect();
+
+    non_dominated(points)
+}
+
+// Drop dominated points and duplicates
+fn non_dominated(mut points: Vec<Vec<f64>>) -> Vec<Vec<f64>> {
+    points.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    points.dedup();
+
+    let kept: Vec<bool> = points
+        .iter()
+        .map(|p| !points.iter().any(|q| dominates(q, p)))
+        .collect();
+
+    points
+        .into_iter()
+        .zip(kept)
+        .filter_map(|(p, k)| k.then_some(p))
+        .collect()
+}
+
+// The WFG algorithm: the sum of exclusive contributions of every point,
+// each one being its own box minus the volume of the following points limited by it
+fn wfg(mut points: Vec<Vec<f64>>, reference: &[f64]) -> f64 {
+    match reference.len() {
+        _ if points.is_empty() => return 0.,
+        1 => return reference[0] - points.iter().map(|p| p[0]).fold(f64::INFINITY, f64::min),
+        2 => return sweep(points, reference),
+        _ => {}
+    }
+
+    // Points sorted by the last objective produce smaller limited sets
+    let last = reference.len() - 1;
+    points.sort_by(|a, b| b[last].partial_cmp(&a[last]).unwrap_or(Ordering::Equal));
+
+    (0..points.len())
+        .map(|i| {
+            let limited: Vec<Vec<f64>> = points[i + 1..]
+                .iter()
+                .map(|q| q.iter().zip(&points[i]).map(|(a, b)| a.max(*b)).collect())
+                .collect();
+
+            inclusive(&points[i], reference) - wfg(non_dominated(limited), reference)
+        })
+        .sum()
+}
+
+// The volume of the box between a point and the reference point
+fn inclusive(point: &[f64], reference: &[f64]) -> f64 {
+    point.iter().zip(reference).map(|(p, r)| r - p).product()
+}
+
+// The area dominated by non-dominated two-objective points
+fn sweep(mut points: Vec<Vec<f64>>, reference: &[f64]) -> f64 {
+    points.sort_by(|a, b| a[0].partial_cmp(&b[0]).unwrap_or(Ordering::Equal));
+
+    let mut area = 0.;
+    let mut top = reference[1];
+
+    for p in points {
+        if p[1] < top {
+            area += (reference[0] - p[0]) * (top - p[1]);
+            top = p[1];
+        }
+    }
+
+    area
+}
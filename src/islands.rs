@@ -0,0 +1,302 @@
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::thread;
+use std::time::Instant;
+
+use crate::archive::ParetoArchive;
+use crate::result::{OptimizationResult, ParetoSolution, Termination};
+use crate::{Direction, Evaluator, NSGAOptimizer, Solution};
+
+/// The way migrants travel between islands
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Topology {
+    /// Every island sends its migrants to the next one, the last one to the first one
+    #[default]
+    Ring,
+    /// Every island sends its migrants to all the other islands
+    FullyConnected,
+}
+
+type Factory<'a, S> = dyn Fn(usize) -> NSGAOptimizer<'a, S> + Sync + 'a;
+
+/// The island model: several optimizers evolving separate populations
+/// in parallel threads and periodically exchanging their best solutions.
+///
+/// Every island is an [`NSGAOptimizer`] produced by a factory, which receives
+/// the island index and can set up different meta params, seeds or survival
+/// strategies for different islands:
+/// ```ignore
+/// let result = Islands::new(4, |i| NSGAOptimizer::new(meta()).seed(i as u64))
+///     .topology(Topology::Ring)
+///     .migration(10, 2)
+///     .optimize(|_| Box::new(DefaultEvaluator::new(100)));
+/// ```
+///
+/// Optimizers are created and run within their threads,
+/// so they don't have to be `Send`.
+pub struct Islands<'a, S: Solution> {
+    count: usize,
+    factory: Box<Factory<'a, S>>,
+    topology: Topology,
+    interval: usize,
+    migrants: usize,
+    archive: Option<ParetoArchive<S>>,
+}
+
+// Sent by an island after every epoch
+struct Report<S> {
+    emigrants: Vec<ParetoSolution<S>>,
+    termination: Option<Termination>,
+}
+
+// Migrants travel with their values, receivers don't evaluate them again
+enum Message<S> {
+    Migrants(Vec<ParetoSolution<S>>),
+    Stop,
+}
+
+// The coordinator's ends of the channels to an island
+type Link<S> = (Receiver<Report<S>>, Sender<Message<S>>);
+
+// What's left of an island after the optimization
+struct Outcome<S> {
+    solutions: Vec<ParetoSolution<S>>,
+    directions: Vec<Direction>,
+    generations: usize,
+    evaluations: usize,
+    population_size: usize,
+}
+
+impl<'a, S> Islands<'a, S>
+where
+    S: Solution,
+{
+    /// Set up `count` islands, every one of them running an optimizer
+    /// produced by `factory` for the island index
+    pub fn new(count: usize, factory: impl Fn(usize) -> NSGAOptimizer<'a, S> + Sync + 'a) -> Self {
+        Islands {
+            count: count.max(1),
+            factory: Box::new(factory),
+            topology: Topology::default(),
+            interval: 10,
+            migrants: 1,
+            archive: None,
+        }
+    }
+
+    /// Set a migration topology, [`Topology::Ring`] by default
+    pub fn topology(mut self, topology: Topology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    /// Migrate `migrants` of the best solutions of every island
+    /// after every `interval` generations, 1 solution every 10 generations by default
+    pub fn migration(mut self, interval: usize, migrants: usize) -> Self {
+        self.interval = interval.max(1);
+        self.migrants = migrants;
+        self
+    }
+
+    /// Set an archive to merge the best solutions of all the islands into,
+    /// one keeping at most as many solutions as all the island populations
+    /// together by default, see [`NSGAOptimizer::archive()`]
+    pub fn archive(mut self, archive: ParetoArchive<S>) -> Self {
+        self.archive = Some(archive);
+        self
+    }
+
+    /// Run all the islands using evaluators produced by `eval`
+    /// for the island index to determine termination condition.
+    ///
+    /// An island that can terminate stops evolving, the optimization stops
+    /// when all of them do, or as soon as any of them finds a good enough solution.
+    /// The result contains the non-dominated solutions of all the islands.
+    pub fn optimize(
+        &mut self,
+        eval: impl Fn(usize) -> Box<dyn Evaluator> + Sync,
+    ) -> OptimizationResult<S> {
+        let started = Instant::now();
+
+        let (termination, outcomes) = thread::scope(|scope| {
+            let mut islands = Vec::with_capacity(self.count);
+            let mut handles = Vec::with_capacity(self.count);
+
+            for island in 0..self.count {
+                let (message_tx, message_rx) = channel();
+                let (report_tx, report_rx) = channel();
+                let (factory, eval) = (&self.factory, &eval);
+                let (interval, migrants) = (self.interval, self.migrants);
+
+                islands.push((report_rx, message_tx));
+                handles.push(scope.spawn(move || {
+                    let optimizer = factory(island);
+                    run(
+                        optimizer,
+                        eval(island),
+                        interval,
+                        migrants,
+                        report_tx,
+                        message_rx,
+                    )
+                }));
+            }
+
+            let termination = self.coordinate(&islands);
+
+            let outcomes: Vec<_> = handles
+                .into_iter()
+                .map(|h| h.join().expect("island thread panicked"))
+                .collect();
+
+            (termination, outcomes)
+        });
+
+        let population: usize = outcomes.iter().map(|o| o.population_size).sum();
+        let archive = self
+            .archive
+            .get_or_insert_with(|| ParetoArchive::new().capacity(population));
+
+        let mut directions = Vec::new();
+        let mut generations = 0;
+        let mut evaluations = 0;
+
+        for outcome in outcomes {
+            directions = outcome.directions;
+            generations = generations.max(outcome.generations);
+            evaluations += outcome.evaluations;
+
+            for s in outcome.solutions {
+                archive.insert_with_violation(s.solution, s.values, s.violation);
+            }
+        }
+
+        OptimizationResult::new(
+            archive.iter().cloned().collect(),
+            directions,
+            generations,
+            evaluations,
+            started.elapsed(),
+            termination,
+        )
+    }
+
+    // Route migrants after every epoch until the islands are done
+    fn coordinate(&self, islands: &[Link<S>]) -> Termination {
+        let stop = || {
+            for (_, messages) in islands {
+                // The island might be gone already after a panic
+                let _ = messages.send(Message::Stop);
+            }
+        };
+
+        loop {
+            let mut epoch = Vec::with_capacity(self.count);
+
+            for (reports, _) in islands {
+                match reports.recv() {
+                    Ok(report) => epoch.push(report),
+                    Err(_) => {
+                        // The island panicked, the panic is propagated when it's joined
+                        stop();
+                        return Termination::Evaluator;
+                    }
+                }
+            }
+
+            let terminations: Vec<_> = epoch.iter().map(|r| r.termination).collect();
+
+            let termination = if terminations.contains(&Some(Termination::GoodEnough)) {
+                Some(Termination::GoodEnough)
+            } else if terminations.iter().all(|t| t.is_some()) {
+                let mut stopped = terminations.iter().flatten().copied();
+
+                stopped
+                    .clone()
+                    .find(|t| *t == Termination::Evaluator)
+                    .or_else(|| stopped.next())
+            } else {
+                None
+            };
+
+            if let Some(termination) = termination {
+                stop();
+                return termination;
+            }
+
+            let mut incoming: Vec<Vec<ParetoSolution<S>>> = vec![Vec::new(); self.count];
+
+            for (from, report) in epoch.into_iter().enumerate() {
+                match self.topology {
+                    Topology::Ring => {
+                        incoming[(from + 1) % self.count].extend(report.emigrants);
+                    }
+                    Topology::FullyConnected => {
+                        for (to, sols) in incoming.iter_mut().enumerate() {
+                            if to != from {
+                                sols.extend(report.emigrants.iter().cloned());
+                            }
+                        }
+                    }
+                }
+            }
+
+            for ((_, messages), sols) in islands.iter().zip(incoming) {
+                let _ = messages.send(Message::Migrants(sols));
+            }
+        }
+    }
+}
+
+// Evolve a single island, reporting to the coordinator after every epoch
+fn run<S: Solution>(
+    mut optimizer: NSGAOptimizer<S>,
+    mut eval: Box<dyn Evaluator>,
+    interval: usize,
+    migrants: usize,
+    reports: Sender<Report<S>>,
+    messages: Receiver<Message<S>>,
+) -> Outcome<S> {
+    let started = Instant::now();
+    let mut termination = None;
+
+    if optimizer.population_is_empty() {
+        optimizer.step();
+    }
+
+    loop {
+        for _ in 0..interval {
+            if termination.is_none() {
+                termination = optimizer.termination(&mut *eval, started);
+            }
+
+            if termination.is_some() {
+                break;
+            }
+
+            optimizer.step();
+        }
+
+        let report = Report {
+            emigrants: optimizer.emigrants(migrants),
+            termination,
+        };
+
+        if reports.send(report).is_err() {
+            break;
+        }
+
+        match messages.recv() {
+            Ok(Message::Migrants(sols)) if termination.is_none() => optimizer.immigrate(sols),
+            Ok(Message::Migrants(_)) => {}
+            Ok(Message::Stop) | Err(_) => break,
+        }
+    }
+
+    Outcome {
+        solutions: optimizer.archive_solutions().cloned().collect(),
+        directions: optimizer.directions(),
+        generations: optimizer.generation(),
+        evaluations: optimizer.evaluations(),
+        population_size: optimizer.population_size(),
+    }
+}
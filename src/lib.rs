@@ -8,38 +8,104 @@
 //! not necessarily just for optimizing pure mathematical functions.
 //!
 //! A short tutorial is avaialble [here](https://github.com/syhpoon/nsga).
+//!
+//! ## Features
+//!
+//! * `parallel` - evaluate objectives of every offspring population
+//!   on a [rayon](https://docs.rs/rayon) thread pool.
+//! * `serde` - save the optimizer state with `NSGAOptimizer::save()` and continue
+//!   from it with `NSGAOptimizer::resume()`. [`CandidateF64`] becomes serializable.
 
+mod algorithm;
+mod archive;
 mod candidate_f64;
+mod duplicates;
 mod evaluator;
+pub mod hypervolume;
+mod islands;
+pub mod metrics;
+mod moead;
+mod observer;
 mod optimizer;
+pub mod problems;
+mod result;
+mod selection;
+mod sorting;
+mod spea2;
+mod survival;
 
+use rand::RngCore;
 use std::fmt::Debug;
 
+pub use algorithm::Algorithm;
+pub use archive::ParetoArchive;
 pub use candidate_f64::{CandidateF64, ParamsF64};
-pub use evaluator::{DefaultEvaluator, Evaluator};
+pub use duplicates::Duplicates;
+pub use evaluator::{
+    All, Any, DefaultEvaluator, Evaluator, HypervolumeStagnation, MaxEvaluations, MaxGenerations,
+    Progress, TargetValues, TimeBudget,
+};
+pub use islands::{Islands, Topology};
+pub use moead::{MOEADOptimizer, Scalarization};
+pub use observer::{Generation, Individual, Observer};
 pub use optimizer::NSGAOptimizer;
+pub use result::{OptimizationResult, ParetoSolution, Termination};
+pub use selection::{ConstrainedTournament, CrowdedTournament, RandomSelection, Selection};
+pub use spea2::SPEA2Optimizer;
+pub use survival::Survival;
+
+/// A function computing the values of all the objectives of a solution,
+/// see [`Meta::values()`]
+pub type Values<S> = dyn Fn(&S) -> Vec<f64> + Send + Sync;
 
 /// A simple ratio type
 pub struct Ratio(pub u32, pub u32);
 
 /// Solution represents a candidate solution.
-pub trait Solution: Debug + Clone {
+///
+/// Operators receive the optimizer's random number generator,
+/// which should be used for every random decision to keep seeded runs reproducible.
+///
+/// Solutions, as well as objectives and constraints, have to be `Send` and `Sync`
+/// so they can be evaluated in parallel and moved between islands.
+pub trait Solution: Debug + Clone + Send + Sync {
     /// Perform a crossover operation with another solution candidate
-    fn crossover(&mut self, other: &mut Self);
+    fn crossover(&mut self, other: &mut Self, rng: &mut dyn RngCore);
 
     /// Mutate a solution candidate
-    fn mutate(&mut self);
+    fn mutate(&mut self, rng: &mut dyn RngCore);
+}
+
+/// Whether an objective should be minimized or maximized
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Direction {
+    #[default]
+    Minimize,
+    Maximize,
 }
 
 /// An optimization objective trait
-pub trait Objective<S: Solution> {
+pub trait Objective<S: Solution>: Send + Sync {
     /// An objective fitness function.
     ///
     /// Given a solution, it should return its fitness score.
-    /// The optimizer always finds a minimum, so if your case requires finding the
-    /// maximum instead, simply multiply the value by -1 before returning.
+    /// Whether it's minimized or maximized is decided by [`Objective::direction()`].
     fn value(&self, sol: &S) -> f64;
 
+    /// The direction of the optimization, [`Direction::Minimize`] by default:
+    /// ```ignore
+    /// fn direction(&self) -> Direction {
+    ///     Direction::Maximize
+    /// }
+    /// ```
+    ///
+    /// Values are reported as returned by [`Objective::value()`] everywhere:
+    /// in the results, to evaluators, observers and [`Objective::good_enough()`].
+    fn direction(&self) -> Direction {
+        Direction::Minimize
+    }
+
     /// A function used for early termination, when applicable.
     ///
     /// When in a particular objective the target value is known,
@@ -55,14 +121,24 @@ pub trait Objective<S: Solution> {
     }
 }
 
-/// A trait that can alter computed fitness score for a solution
+/// An optimization constraint
 ///
-/// It is often used to exclude (by returning [`f64::MAX`]) solutions
-/// based on some complex logic
-/// that is too cumbersome to include in the [`Objective::value()`]
-pub trait Constraint<S: Solution> {
-    /// Process a currently computed value for a given solution
-    fn value(&self, sol: &S, val: f64) -> f64;
+/// Constraints are handled using Deb's constraint-domination:
+/// a feasible solution is always preferred over an infeasible one,
+/// and among infeasible solutions the one with a smaller total violation wins.
+/// This gives the optimizer a gradient towards the feasible region
+/// instead of lumping all the infeasible solutions together.
+pub trait Constraint<S: Solution>: Send + Sync {
+    /// Return by how much a solution violates the constraint.
+    ///
+    /// Zero (or a negative value) means the constraint is satisfied:
+    /// ```ignore
+    /// // x <= 5
+    /// fn violation(&self, sol: &Candidate) -> f64 {
+    ///     sol.x - 5.
+    /// }
+    /// ```
+    fn violation(&self, sol: &S) -> f64;
 }
 
 /// A trait to implement a set of meta-parameters for the optimization
@@ -79,25 +155,113 @@ pub trait Meta<'a, S: Solution> {
     /// A ratio to determine how often to perform a mutation operation
     fn mutation_odds(&self) -> &'a Ratio;
 
-    /// Return a random solution
-    fn random_solution(&mut self) -> S;
+    /// Return a random solution generated using the provided `rng`
+    fn random_solution(&mut self, rng: &mut dyn RngCore) -> S;
 
     /// Return a list of objectives to use in optimization.
     /// Cannot be empty
     fn objectives(&self) -> &Vec<Box<dyn Objective<S> + 'a>>;
 
+    /// Return a function computing the values of all the objectives at once,
+    /// in the order of [`Meta::objectives()`].
+    ///
+    /// Optimizers call it instead of every [`Objective::value()`] when provided,
+    /// which saves work when the objectives share most of it. `None` by default.
+    fn values(&self) -> Option<&Values<S>> {
+        None
+    }
+
     /// Return an optional list of optimization constraints
     fn constraints(&self) -> &Vec<Box<dyn Constraint<S> + 'a>>;
 }
 
 #[cfg(test)]
 mod test {
-    use crate::Ratio;
+    use crate::{
+        CandidateF64, Direction, Objective, OptimizationResult, ParamsF64, ParetoSolution, Ratio,
+        Termination,
+    };
+    use std::time::Duration;
 
     pub(crate) const POPULATION_SIZE: usize = 20;
     pub(crate) const CROSSOVER_ODDS: Ratio = Ratio(6, 10);
     pub(crate) const MUTATION_ODDS: Ratio = Ratio(3, 10);
+
+    // Schaffer's SCH problem, the optimal solutions are in [0, 2]
+    pub(crate) struct SchObjective1 {}
+
+    impl Objective<CandidateF64> for SchObjective1 {
+        fn value(&self, candidate: &CandidateF64) -> f64 {
+            candidate.val * candidate.val
+        }
+    }
+
+    pub(crate) struct SchObjective2 {}
+
+    impl Objective<CandidateF64> for SchObjective2 {
+        fn value(&self, candidate: &CandidateF64) -> f64 {
+            let x = candidate.val - 2.;
+            x * x
+        }
+    }
+
+    pub(crate) fn sch_meta() -> ParamsF64<'static> {
+        ParamsF64 {
+            population_size: POPULATION_SIZE,
+            crossover_odds: &CROSSOVER_ODDS,
+            mutation_odds: &MUTATION_ODDS,
+            objectives: vec![Box::new(SchObjective1 {}), Box::new(SchObjective2 {})],
+            constraints: vec![],
+            val_range: -55.0..56.0,
+        }
+    }
+
+    // Check that an optimization of SCH found its front, both ends included
+    pub(crate) fn assert_sch_front(res: &OptimizationResult<CandidateF64>) {
+        assert!(res.solutions.len() >= POPULATION_SIZE / 2);
+
+        let vals: Vec<_> = res.iter().map(|x| x.solution.val).collect();
+        assert!(vals.iter().all(|x| *x >= -0.1 && *x <= 2.1), "{:?}", vals);
+
+        assert!(vals.iter().any(|x| *x < 0.1) && vals.iter().any(|x| *x > 1.9));
+    }
+
+    // A result made of solutions with the given values, oriented for minimization,
+    // and constraint violations
+    pub(crate) fn values_result(
+        solutions: Vec<(Vec<f64>, f64)>,
+        directions: Vec<Direction>,
+    ) -> OptimizationResult<()> {
+        OptimizationResult::new(
+            solutions
+                .into_iter()
+                .map(|(values, violation)| ParetoSolution {
+                    solution: (),
+                    values,
+                    front: 0,
+                    violation,
+                })
+                .collect(),
+            directions,
+            0,
+            0,
+            Duration::ZERO,
+            Termination::Evaluator,
+        )
+    }
 }
 
+mod test_archive;
+mod test_candidate_f64;
+mod test_evaluator;
+mod test_hypervolume;
+mod test_islands;
+mod test_metrics;
+mod test_moead;
+mod test_problems;
 mod test_sch;
+mod test_selection;
+mod test_sorting;
+mod test_spea2;
 mod test_sum;
+mod test_survival;
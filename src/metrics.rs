@@ -0,0 +1,252 @@
+//! Convergence and diversity metrics of approximated Pareto fronts.
+//!
+//! Convergence metrics measure the distance between a front and a reference one,
+//! usually the true Pareto front sampled densely enough:
+//! ```
+//! use nsga::metrics::{generational_distance, inverted_generational_distance};
+//!
+//! let reference = vec![vec![0., 1.], vec![0.5, 0.5], vec![1., 0.]];
+//! let front = vec![vec![0., 1.], vec![1., 0.]];
+//!
+//! assert_eq!(generational_distance(&front, &reference), 0.);
+//! assert!(inverted_generational_distance(&front, &reference) > 0.);
+//! ```
+//!
+//! Both fronts can be either raw objective values or an [`OptimizationResult`],
+//! see [`Front`]. Objective values are minimized unless the front declares
+//! otherwise, lower values of the metrics are better except for [`maximum_spread()`].
+
+use std::cmp::Ordering;
+
+use crate::result::OptimizationResult;
+use crate::Direction;
+
+/// A set of objective vectors the metrics are computed for
+pub trait Front {
+    /// Objective values of every point
+    fn points(&self) -> Vec<&[f64]>;
+
+    /// Directions of the objectives, all of them are minimized if empty
+    fn directions(&self) -> &[Direction] {
+        &[]
+    }
+}
+
+impl<V: AsRef<[f64]>> Front for [V] {
+    fn points(&self) -> Vec<&[f64]> {
+        self.iter().map(|v| v.as_ref()).collect()
+    }
+}
+
+impl<V: AsRef<[f64]>> Front for Vec<V> {
+    fn points(&self) -> Vec<&[f64]> {
+        self.as_slice().points()
+    }
+}
+
+/// Only the feasible solutions are taken into account
+impl<S> Front for OptimizationResult<S> {
+    fn points(&self) -> Vec<&[f64]> {
+        self.iter()
+            .filter(|s| s.is_feasible())
+            .map(|s| s.values.as_slice())
+            .collect()
+    }
+
+    fn directions(&self) -> &[Direction] {
+        &self.directions
+    }
+}
+
+/// Generational distance (GD): the average Euclidean distance from every point
+/// of `front` to the closest point of `reference`.
+///
+/// It shows how close the front is to the reference one, but not how well it
+/// covers it. Infinite for an empty front.
+pub fn generational_distance(
+    front: &(impl Front + ?Sized),
+    reference: &(impl Front + ?Sized),
+) -> f64 {
+    average_distance(&front.points(), &reference.points(), distance)
+}
+
+/// Inverted generational distance (IGD): the average Euclidean distance from every
+/// point of `reference` to the closest point of `front`.
+///
+/// Unlike [`generational_distance()`] it's only low for fronts both close to
+/// the reference one and covering it. Infinite for an empty front.
+pub fn inverted_generational_distance(
+    front: &(impl Front + ?Sized),
+    reference: &(impl Front + ?Sized),
+) -> f64 {
+    average_distance(&reference.points(), &front.points(), distance)
+}
+
+/// [IGD+](https://doi.org/10.1007/978-3-319-15892-1_8): the inverted generational
+/// distance counting only the objectives where a point of `front` is worse
+/// than a point of `reference`, according to the directions of `front`.
+///
+/// Points dominating the reference ones are at zero distance from them,
+/// which makes the metric weakly Pareto compliant. Infinite for an empty front.
+pub fn inverted_generational_distance_plus(
+    front: &(impl Front + ?Sized),
+    reference: &(impl Front + ?Sized),
+) -> f64 {
+    let directions = front.directions();
+
+    average_distance(&reference.points(), &front.points(), |z, a| {
+        a.iter()
+            .zip(z)
+            .enumerate()
+            .map(|(i, (a, z))| match directions.get(i) {
+                Some(Direction::Maximize) => (z - a).max(0.).powi(2),
+                _ => (a - z).max(0.).powi(2),
+            })
+            .sum::<f64>()
+            .sqrt()
+    })
+}
+
+/// Schott's spacing: the standard deviation of the Manhattan distances
+/// from every point to its closest neighbour.
+///
+/// Zero for evenly spaced points and for fronts of fewer than two points.
+pub fn spacing(front: &(impl Front + ?Sized)) -> f64 {
+    let points = front.points();
+    if points.len() < 2 {
+        return 0.;
+    }
+
+    let manhattan = |a: &[f64], b: &[f64]| a.iter().zip(b).map(|(x, y)| (x - y).abs()).sum();
+    let closest = nearest_distances(&points, manhattan);
+    let mean = closest.iter().sum::<f64>() / closest.len() as f64;
+
+    (closest.iter().map(|d| (d - mean).powi(2)).sum::<f64>() / (closest.len() - 1) as f64).sqrt()
+}
+
+/// [Deb's spread Δ](https://doi.org/10.1109/4235.996017): how uniformly
+/// `front` is spread and how far it reaches the extremes of `reference`.
+///
+/// For two objectives the points are sorted by the first one and
+/// the distances between consecutive points are compared to their average,
+/// the distances from the extreme points of `reference` to the closest points
+/// of `front` are added to the sum, the best values being decided by the
+/// directions of `front`. For more objectives the distances to the
+/// closest neighbours are used instead, as in the
+/// [generalized spread](https://doi.org/10.1007/978-3-540-70928-2_64).
+///
+/// Zero for evenly spaced points covering the reference front from end to end,
+/// usually less than 1. It's 1 for an empty front.
+pub fn spread(front: &(impl Front + ?Sized), reference: &(impl Front + ?Sized)) -> f64 {
+    let directions = front.directions();
+    let mut points = front.points();
+    let reference = reference.points();
+
+    if points.is_empty() || reference.is_empty() {
+        return 1.;
+    }
+
+    let objectives = points[0].len();
+
+    // Distances from the best point of the reference front in every objective
+    let extremes: f64 = (0..objectives)
+        .map(|i| {
+            let extreme = reference
+                .iter()
+                .min_by(|a, b| {
+                    let (a, b) = match directions.get(i) {
+                        Some(Direction::Maximize) => (-a[i], -b[i]),
+                        _ => (a[i], b[i]),
+                    };
+
+                    a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+                })
+                .unwrap();
+
+            closest(extreme, &points, distance)
+        })
+        .sum();
+
+    let distances = if objectives == 2 {
+        points.sort_by(|a, b| a[0].partial_cmp(&b[0]).unwrap_or(Ordering::Equal));
+        points.windows(2).map(|w| distance(w[0], w[1])).collect()
+    } else if points.len() > 1 {
+        nearest_distances(&points, distance)
+    } else {
+        Vec::new()
+    };
+
+    let mean = if distances.is_empty() {
+        0.
+    } else {
+        distances.iter().sum::<f64>() / distances.len() as f64
+    };
+
+    let deviation: f64 = distances.iter().map(|d| (d - mean).abs()).sum();
+    let denominator = extremes + distances.len() as f64 * mean;
+
+    if denominator == 0. {
+        return 0.;
+    }
+
+    (extremes + deviation) / denominator
+}
+
+/// Zitzler's maximum spread: the length of the diagonal of the box
+/// bounding `front`, larger meaning a wider front.
+///
+/// Zero for an empty front.
+pub fn maximum_spread(front: &(impl Front + ?Sized)) -> f64 {
+    let points = front.points();
+    let objectives = points.first().map_or(0, |p| p.len());
+
+    (0..objectives)
+        .map(|i| {
+            let (min, max) = points
+                .iter()
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), p| {
+                    (min.min(p[i]), max.max(p[i]))
+                });
+
+            (max - min).powi(2)
+        })
+        .sum::<f64>()
+        .sqrt()
+}
+
+fn distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter()
+        .zip(b)
+        .map(|(x, y)| (x - y).powi(2))
+        .sum::<f64>()
+        .sqrt()
+}
+
+// The distance from `point` to the closest of `others`
+fn closest(point: &[f64], others: &[&[f64]], dist: impl Fn(&[f64], &[f64]) -> f64) -> f64 {
+    others
+        .iter()
+        .map(|o| dist(point, o))
+        .fold(f64::INFINITY, f64::min)
+}
+
+// The average distance from every one of `from` to the closest of `to`
+fn average_distance(from: &[&[f64]], to: &[&[f64]], dist: impl Fn(&[f64], &[f64]) -> f64) -> f64 {
+    if from.is_empty() {
+        return if to.is_empty() { 0. } else { f64::INFINITY };
+    }
+
+    from.iter().map(|p| closest(p, to, &dist)).sum::<f64>() / from.len() as f64
+}
+
+// The distance from every point to its closest neighbour
+fn nearest_distances(points: &[&[f64]], dist: impl Fn(&[f64], &[f64]) -> f64) -> Vec<f64> {
+    (0..points.len())
+        .map(|i| {
+            (0..points.len())
+                .filter(|j| *j != i)
+                .map(|j| dist(points[i], points[j]))
+                .fold(f64::INFINITY, f64::min)
+        })
+        .collect()
+}
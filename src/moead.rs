@@ -0,0 +1,330 @@
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use std::time::Instant;
+
+use crate::archive::ParetoArchive;
+use crate::optimizer::{directions, evaluate, good_enough, oriented};
+use crate::result::{OptimizationResult, Termination};
+use crate::survival::reference_directions;
+use crate::{Evaluator, Meta, Progress, Solution};
+
+/// A way to turn objective values into a single value for a weight vector
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Scalarization {
+    /// The largest weighted distance to the ideal point along any objective
+    #[default]
+    Tchebycheff,
+    /// Penalty-based boundary intersection: the distance to the ideal point
+    /// along the weight vector plus `penalty` times the distance from it.
+    /// A penalty of 5 is a common choice.
+    PBI { penalty: f64 },
+}
+
+impl Scalarization {
+    fn value(&self, values: &[f64], weights: &[f64], ideal: &[f64]) -> f64 {
+        match self {
+            Scalarization::Tchebycheff => values
+                .iter()
+                .zip(weights)
+                .zip(ideal)
+                .map(|((v, w), z)| w.max(1e-6) * (v - z).abs())
+                .fold(f64::NEG_INFINITY, f64::max),
+            Scalarization::PBI { penalty } => {
+                let norm = weights.iter().map(|w| w * w).sum::<f64>().sqrt();
+                let diff: Vec<f64> = values.iter().zip(ideal).map(|(v, z)| v - z).collect();
+
+                let d1 = diff.iter().zip(weights).map(|(d, w)| d * w).sum::<f64>() / norm;
+                let d2 = diff
+                    .iter()
+                    .zip(weights)
+                    .map(|(d, w)| (d - d1 * w / norm).powi(2))
+                    .sum::<f64>()
+                    .sqrt();
+
+                d1 + penalty * d2
+            }
+        }
+    }
+}
+
+// A subproblem: a weight vector along with its current solution
+struct Subproblem<S> {
+    weights: Vec<f64>,
+    neighbours: Vec<usize>,
+    sol: S,
+    values: Vec<f64>,
+    violation: f64,
+}
+
+/// [MOEA/D](https://doi.org/10.1109/TEVC.2007.892759) optimizer:
+/// decomposes a multi-objective problem into scalar subproblems along
+/// evenly spread weight vectors, each of them optimized using its neighbours.
+///
+/// It works with the same [`Meta`] params as [`crate::NSGAOptimizer`], so the two
+/// can be swapped without changing the problem definition. There's one subproblem
+/// per weight vector, by default the smallest number of Das and Dennis's weight
+/// vectors not less than [`Meta::population_size()`].
+///
+/// It needs at least two objectives.
+///
+/// Constraints are handled the same way: the smaller total violation wins,
+/// the scalarized values are compared only between equally feasible solutions.
+pub struct MOEADOptimizer<'a, S: Solution> {
+    meta: Box<dyn Meta<'a, S> + 'a>,
+    rng: ChaCha8Rng,
+    scalarization: Scalarization,
+    divisions: Option<usize>,
+    neighbourhood: usize,
+    replacements: usize,
+    archive: ParetoArchive<S>,
+    subproblems: Vec<Subproblem<S>>,
+    ideal: Vec<f64>,
+    generation: usize,
+    evaluations: usize,
+}
+
+impl<'a, S> MOEADOptimizer<'a, S>
+where
+    S: Solution,
+{
+    /// Instantiate a new optimizer with a given meta params
+    pub fn new(meta: impl Meta<'a, S> + 'a) -> Self {
+        let archive = ParetoArchive::new().capacity(meta.population_size());
+
+        MOEADOptimizer {
+            meta: Box::new(meta),
+            rng: ChaCha8Rng::from_entropy(),
+            scalarization: Scalarization::default(),
+            divisions: None,
+            neighbourhood: 20,
+            replacements: 2,
+            archive,
+            subproblems: Vec::new(),
+            ideal: Vec::new(),
+            generation: 0,
+            evaluations: 0,
+        }
+    }
+
+    /// Seed the random number generator, see [`crate::NSGAOptimizer::seed()`]
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.rng = ChaCha8Rng::seed_from_u64(seed);
+        self
+    }
+
+    /// Set a scalarization function, [`Scalarization::Tchebycheff`] by default
+    pub fn scalarization(mut self, scalarization: Scalarization) -> Self {
+        self.scalarization = scalarization;
+        self
+    }
+
+    /// Generate weight vectors with the given number of `divisions` along every objective,
+    /// producing `C(M + divisions - 1, divisions)` subproblems for `M` objectives
+    pub fn divisions(mut self, divisions: usize) -> Self {
+        self.divisions = Some(divisions.max(1));
+        self
+    }
+
+    /// Set the number of the closest subproblems used for mating and replacement,
+    /// 20 by default
+    pub fn neighbourhood(mut self, size: usize) -> Self {
+        self.neighbourhood = size.max(2);
+        self
+    }
+
+    /// Set the maximum number of neighbours a child can replace, 2 by default.
+    ///
+    /// Lower values keep the population diverse.
+    pub fn replacements(mut self, replacements: usize) -> Self {
+        self.replacements = replacements.max(1);
+        self
+    }
+
+    /// Set an archive to keep the best solutions found during the optimization,
+    /// see [`crate::NSGAOptimizer::archive()`]
+    pub fn archive(mut self, archive: ParetoArchive<S>) -> Self {
+        self.archive = archive;
+        self
+    }
+
+    /// Run an optimization process using `eval` to determine termination condition,
+    /// see [`crate::NSGAOptimizer::optimize()`]
+    pub fn optimize(&mut self, mut eval: Box<dyn Evaluator>) -> OptimizationResult<S> {
+        let started = Instant::now();
+
+        if self.subproblems.is_empty() {
+            self.initialize();
+        }
+
+        let termination = loop {
+            // Check if there's a good-enough solution already
+            let objectives = self.meta.objectives();
+            if self
+                .subproblems
+                .iter()
+                .any(|p| good_enough(objectives, &p.values, p.violation))
+            {
+                break Termination::GoodEnough;
+            }
+
+            // Check if we can already terminate
+            let progress = Progress::new(
+                directions(objectives),
+                self.generation,
+                self.evaluations,
+                started.elapsed(),
+                self.subproblems
+                    .iter()
+                    .map(|p| (p.values.as_slice(), p.violation)),
+            );
+
+            if eval.can_terminate(&progress) {
+                break Termination::Evaluator;
+            }
+
+            self.step();
+        };
+
+        OptimizationResult::new(
+            self.archive.iter().cloned().collect(),
+            directions(self.meta.objectives()),
+            self.generation,
+            self.evaluations,
+            started.elapsed(),
+            termination,
+        )
+    }
+
+    /// Return the best solutions found so far
+    pub fn solutions(&self) -> impl Iterator<Item = &S> {
+        self.archive.iter().map(|s| &s.solution)
+    }
+
+    fn initialize(&mut self) {
+        let m = self.meta.objectives().len();
+        let pop_size = self.meta.population_size();
+
+        // The fewest divisions producing at least `pop_size` weight vectors
+        let divisions = self.divisions.unwrap_or_else(|| {
+            (1..pop_size)
+                .find(|p| reference_directions(m, *p).len() >= pop_size)
+                .unwrap_or(pop_size)
+        });
+
+        let weights = reference_directions(m, divisions);
+        let t = self.neighbourhood.min(weights.len());
+
+        for w in weights.iter() {
+            let mut closest: Vec<usize> = (0..weights.len()).collect();
+            closest.sort_by(|a, b| {
+                distance(w, &weights[*a])
+                    .partial_cmp(&distance(w, &weights[*b]))
+                    .unwrap()
+            });
+            closest.truncate(t);
+
+            let sol = self.meta.random_solution(&mut self.rng);
+            let (values, violation) = self.evaluate(&sol);
+
+            self.subproblems.push(Subproblem {
+                weights: w.clone(),
+                neighbours: closest,
+                sol,
+                values,
+                violation,
+            });
+        }
+
+        self.ideal = (0..m)
+            .map(|i| {
+                self.subproblems
+                    .iter()
+                    .map(|p| p.values[i])
+                    .fold(f64::INFINITY, f64::min)
+            })
+            .collect();
+    }
+
+    // Produce a child for every subproblem out of its neighbours
+    // and let it replace the neighbours it improves
+    fn step(&mut self) {
+        let crossover_odds = self.meta.crossover_odds();
+        let mutation_odds = self.meta.mutation_odds();
+
+        for i in 0..self.subproblems.len() {
+            let neighbours = &self.subproblems[i].neighbours;
+            let mut parents = neighbours.choose_multiple(&mut self.rng, 2);
+            let p1 = *parents.next().unwrap();
+            let p2 = *parents.next().unwrap_or(&p1);
+
+            let mut c1 = self.subproblems[p1].sol.clone();
+            let mut c2 = self.subproblems[p2].sol.clone();
+
+            if self.rng.gen_ratio(crossover_odds.0, crossover_odds.1) {
+                c1.crossover(&mut c2, &mut self.rng);
+            }
+
+            if self.rng.gen_ratio(mutation_odds.0, mutation_odds.1) {
+                c1.mutate(&mut self.rng);
+            }
+
+            let (values, violation) = self.evaluate(&c1);
+
+            for (z, v) in self.ideal.iter_mut().zip(&values) {
+                *z = z.min(*v);
+            }
+
+            let mut neighbours = self.subproblems[i].neighbours.clone();
+            neighbours.shuffle(&mut self.rng);
+
+            let mut replaced = 0;
+            for j in neighbours {
+                if replaced == self.replacements {
+                    break;
+                }
+
+                let p = &self.subproblems[j];
+                let better = if violation != p.violation {
+                    violation < p.violation
+                } else {
+                    self.scalarization.value(&values, &p.weights, &self.ideal)
+                        < self.scalarization.value(&p.values, &p.weights, &self.ideal)
+                };
+
+                if better {
+                    let p = &mut self.subproblems[j];
+                    p.sol = c1.clone();
+                    p.values = values.clone();
+                    p.violation = violation;
+
+                    replaced += 1;
+                }
+            }
+        }
+
+        self.generation += 1;
+    }
+
+    // Evaluate a solution and keep it in the archive if it's good enough,
+    // the values are oriented for minimization
+    fn evaluate(&mut self, sol: &S) -> (Vec<f64>, f64) {
+        let objectives = self.meta.objectives();
+        let (values, violation) =
+            evaluate(objectives, self.meta.values(), self.meta.constraints(), sol);
+        let values = oriented(&directions(objectives), &values);
+
+        self.evaluations += 1;
+        self.archive
+            .insert_with_violation(sol.clone(), values.clone(), violation);
+
+        (values, violation)
+    }
+}
+
+fn distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter()
+        .zip(b)
+        .map(|(x, y)| (x - y).powi(2))
+        .sum::<f64>()
+        .sqrt()
+}
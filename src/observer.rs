@@ -0,0 +1,51 @@
+use crate::Solution;
+
+/// A population member as seen by an [`Observer`] or a [`crate::Selection`]
+#[derive(Debug)]
+pub struct Individual<'a, S: Solution> {
+    pub solution: &'a S,
+    /// Objective values, in the order of [`crate::Meta::objectives()`]
+    pub values: &'a [f64],
+    /// Total constraint violation, 0 for feasible solutions
+    pub violation: f64,
+    /// Front rank, 0 being the non-dominated front
+    pub front: usize,
+    /// Crowding distance within the front
+    pub distance: f64,
+}
+
+/// The state of an optimization after a generation
+#[derive(Debug)]
+pub struct Generation<'a, S: Solution> {
+    /// Generation index, 0 being the initial population
+    pub index: usize,
+    /// Current population, sorted by front
+    pub population: Vec<Individual<'a, S>>,
+    /// The number of solutions evaluated so far
+    pub evaluations: usize,
+}
+
+/// A hook called by the optimizer after every generation
+///
+/// Closures taking a [`Generation`] and returning a `bool` are observers too:
+/// ```ignore
+/// let optimizer = NSGAOptimizer::new(meta).observer(|g: &Generation<_>| {
+///     println!("{}: {} evaluations", g.index, g.evaluations);
+///     false
+/// });
+/// ```
+pub trait Observer<S: Solution> {
+    /// Inspect a generation.
+    /// Returns true if an optimization process should be stopped early.
+    fn observe(&mut self, generation: &Generation<S>) -> bool;
+}
+
+impl<S, F> Observer<S> for F
+where
+    S: Solution,
+    F: FnMut(&Generation<S>) -> bool,
+{
+    fn observe(&mut self, generation: &Generation<S>) -> bool {
+        self(generation)
+    }
+}
@@ -1,29 +1,83 @@
 use peeking_take_while::PeekableExt;
 use rand::prelude::*;
-use rand::seq::SliceRandom;
-
-use crate::{Evaluator, Objective};
+use rand_chacha::ChaCha8Rng;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::archive::ParetoArchive;
+use crate::duplicates::{close, Duplicates};
+use crate::observer::{Generation, Individual, Observer};
+use crate::result::{OptimizationResult, ParetoSolution, Termination};
+use crate::selection::{ConstrainedTournament, Selection};
+use crate::sorting::{constrained_dominates, constrained_non_dominated_sort, crowding_distance};
+use crate::survival::{reference_directions, select_by_reference, Survival};
+use crate::{Constraint, Direction, Evaluator, Objective, Progress, Values};
 use std::cmp::Ordering;
-use std::collections::{HashMap, HashSet};
-use std::convert::identity;
+use std::collections::HashSet;
+use std::time::Instant;
+
+use super::{Meta, Solution};
 
-use super::{Meta, Ratio, Solution};
+#[cfg(feature = "serde")]
+mod checkpoint;
 
 type SolutionId = u64;
 
+// Mutated copies tried in place of a duplicate before falling back to a random solution
+const MUTATION_ATTEMPTS: usize = 3;
+
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct Candidate<S: Solution> {
     id: SolutionId,
     sol: S,
+    // Objective values oriented for minimization, computed exactly once
+    values: Vec<f64>,
+    // Objective values as returned by the objectives
+    natural: Vec<f64>,
+    // Total constraint violation, 0 for feasible solutions
+    violation: f64,
     front: usize,
     distance: f64,
 }
 
-/// NSGA-II optimizer
+impl<S: Solution> Candidate<S> {
+    fn individual(&self) -> Individual<'_, S> {
+        Individual {
+            solution: &self.sol,
+            values: &self.natural,
+            violation: self.violation,
+            front: self.front,
+            distance: self.distance,
+        }
+    }
+}
+
+/// NSGA-II optimizer, optionally running NSGA-III survival (see [`Survival`])
 pub struct NSGAOptimizer<'a, S: Solution> {
     meta: Box<dyn Meta<'a, S> + 'a>,
     last_id: SolutionId,
-    best_solutions: Vec<(Vec<f64>, S)>,
+    archive: ParetoArchive<S>,
+    rng: ChaCha8Rng,
+    survival: Survival,
+    selection: Box<dyn Selection<S> + 'a>,
+    duplicates: Option<Duplicates<'a, S>>,
+    // Offspring per step in the steady-state mode
+    steady_state: Option<usize>,
+    // NSGA-III reference directions, generated on the first survival
+    reference_directions: Vec<Vec<f64>>,
+    // Current population, sorted by front
+    population: Vec<Candidate<S>>,
+    // Solutions handed out by `ask` and waiting for their values
+    pending: Vec<S>,
+    // User-supplied members of the initial population
+    warm_start: Vec<S>,
+    generation: usize,
+    evaluations: usize,
+    observers: Vec<Box<dyn Observer<S> + 'a>>,
+    stop_requested: bool,
+    #[cfg(feature = "serde")]
+    checkpoint: Option<checkpoint::Checkpoint<'a, S>>,
 }
 
 impl<'a, S> NSGAOptimizer<'a, S>
@@ -31,328 +85,816 @@ where
     S: Solution,
 {
     /// Instantiate a new optimizer with a given meta params
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`Meta::population_size()`] is zero.
     pub fn new(meta: impl Meta<'a, S> + 'a) -> Self {
+        assert!(
+            meta.population_size() > 0,
+            "population size must be greater than zero"
+        );
+
+        let archive = ParetoArchive::new().capacity(meta.population_size());
+
         NSGAOptimizer {
             meta: Box::new(meta),
             last_id: 0,
-            best_solutions: Vec::new(),
+            archive,
+            rng: ChaCha8Rng::from_entropy(),
+            survival: Survival::default(),
+            selection: Box::new(ConstrainedTournament::default()),
+            duplicates: None,
+            steady_state: None,
+            reference_directions: Vec::new(),
+            population: Vec::new(),
+            pending: Vec::new(),
+            warm_start: Vec::new(),
+            generation: 0,
+            evaluations: 0,
+            observers: Vec::new(),
+            stop_requested: false,
+            #[cfg(feature = "serde")]
+            checkpoint: None,
         }
     }
 
+    /// Seed the random number generator used for every random decision
+    /// made during the optimization, including the ones made by
+    /// [`Solution`] operators and [`Meta::random_solution()`].
+    ///
+    /// Two runs with the same seed and the same meta params
+    /// produce identical results.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.rng = ChaCha8Rng::seed_from_u64(seed);
+        self
+    }
+
+    /// Set a survival strategy, [`Survival::CrowdingDistance`] by default.
+    ///
+    /// Use [`Survival::ReferencePoints`] to run NSGA-III
+    /// for problems with many objectives.
+    pub fn survival(mut self, survival: Survival) -> Self {
+        self.survival = survival;
+        self
+    }
+
+    /// Set a parent selection operator,
+    /// a binary [`ConstrainedTournament`] by default.
+    ///
+    /// Use a larger tournament to increase the selection pressure,
+    /// or a [`crate::RandomSelection`] to remove it entirely.
+    pub fn selection(mut self, selection: impl Selection<S> + 'a) -> Self {
+        self.selection = Box::new(selection);
+        self
+    }
+
+    /// Replace duplicate solutions in the initial population and in the offspring
+    /// with mutated copies or random solutions, duplicates are kept by default.
+    ///
+    /// Discrete problems in particular tend to fill the population
+    /// with copies of a single solution, which stalls the search.
+    pub fn duplicates(mut self, duplicates: Duplicates<'a, S>) -> Self {
+        self.duplicates = Some(duplicates);
+        self
+    }
+
+    /// Switch to the steady-state mode, producing `offspring` children per step
+    /// instead of a whole new population.
+    ///
+    /// Every child is inserted into the population, updating the fronts incrementally,
+    /// and the most crowded member of the last front is dropped. Every step counts
+    /// as a generation, so the evaluator and observers see every step.
+    /// This suits expensive objectives, since the termination condition is checked
+    /// after every `offspring` evaluations.
+    ///
+    /// The survival strategy doesn't apply in this mode.
+    pub fn steady_state(mut self, offspring: usize) -> Self {
+        self.steady_state = Some(offspring.max(1));
+        self
+    }
+
+    /// Set an archive to keep the best solutions found during the optimization.
+    ///
+    /// By default it keeps at most [`Meta::population_size()`] solutions,
+    /// pruned by crowding. Use a larger, an unbounded or an epsilon-dominance
+    /// archive to return more solutions, at the cost of slower insertions.
+    pub fn archive(mut self, archive: ParetoArchive<S>) -> Self {
+        self.archive = archive;
+        self
+    }
+
+    /// Seed the initial population with known solutions,
+    /// e.g. good designs from previous runs.
+    ///
+    /// At most [`Meta::population_size()`] solutions are used,
+    /// the rest of the initial population is filled using [`Meta::random_solution()`].
+    pub fn warm_start(mut self, solutions: impl IntoIterator<Item = S>) -> Self {
+        self.warm_start.extend(solutions);
+        self
+    }
+
+    /// Add an observer called after every generation,
+    /// including the initial population.
+    ///
+    /// An observer can stop the optimization early.
+    pub fn observer(mut self, observer: impl Observer<S> + 'a) -> Self {
+        self.observers.push(Box::new(observer));
+        self
+    }
+
     /// Run an optimization process using `eval` to determine termination condition
     ///
     /// Since an optimization can produce a set of
     /// [Pareto optimal solutions](https://en.wikipedia.org/wiki/Pareto_front),
-    /// the optimizer returns an iterator.
-    pub fn optimize(&mut self, mut eval: Box<dyn Evaluator>) -> impl Iterator<Item = S> {
-        let mut rnd = rand::thread_rng();
+    /// the result contains every one of them along with its objective values.
+    pub fn optimize(&mut self, mut eval: Box<dyn Evaluator>) -> OptimizationResult<S> {
+        let started = Instant::now();
 
-        let pop_size = self.meta.population_size();
-        let crossover_odds = self.meta.crossover_odds();
-        let mutation_odds = self.meta.mutation_odds();
+        // Batches asked for but never told about are dropped
+        self.pending.clear();
 
-        // Initial population
-        let pop: Vec<_> = (0..pop_size)
-            .map(|_| {
-                let id = self.next_id();
-                let sol = self.meta.random_solution();
-
-                Candidate {
-                    id,
-                    sol,
-                    front: 0,
-                    distance: 0.0,
-                }
-            })
-            .collect();
+        if self.population.is_empty() {
+            self.step();
+        }
 
-        let mut parent_pop = self.sort(pop);
+        let termination = loop {
+            if let Some(termination) = self.termination(&mut *eval, started) {
+                break termination;
+            }
 
-        for iter in 0.. {
-            // Keep copies of the best candidates in a stash
-            parent_pop
-                .iter()
-                .take_while(|c| c.front == 0)
-                .for_each(|c| {
-                    let vals: Vec<f64> = self.values(&c.sol);
+            self.step();
+        };
+
+        OptimizationResult::new(
+            self.archive.iter().cloned().collect(),
+            self.directions(),
+            self.generation,
+            self.evaluations,
+            started.elapsed(),
+            termination,
+        )
+    }
 
-                    // Only keep better old values
-                    self.best_solutions
-                        .retain(|s| s.0.iter().zip(&vals).any(|(old, new)| old < new));
+    /// Return the next batch of solutions to evaluate.
+    ///
+    /// Together with [`NSGAOptimizer::tell()`] this allows running the
+    /// optimization loop step by step, evaluating solutions outside of
+    /// the optimizer, e.g. in batch jobs. The first batch is the initial population,
+    /// every following one is the offspring of the current population.
+    /// ```ignore
+    /// for _ in 0..generations {
+    ///     let batch = optimizer.ask();
+    ///     let values = evaluate_somewhere_else(&batch);
+    ///     optimizer.tell(values);
+    /// }
+    ///
+    /// let best: Vec<_> = optimizer.solutions().collect();
+    /// ```
+    pub fn ask(&mut self) -> Vec<S> {
+        self.pending = if self.population.is_empty() {
+            self.initial_population()
+        } else {
+            self.offspring()
+        };
 
-                    self.best_solutions.push((vals, c.sol.clone()));
-                });
+        self.pending.clone()
+    }
 
-            // Check if there's a good-enough solution already
-            if parent_pop
-                .iter()
-                .map(|c| {
-                    self.meta
-                        .objectives()
-                        .iter()
-                        .map(|obj| obj.good_enough(self.value(&c.sol, obj)))
-                        .all(identity)
-                })
-                .any(identity)
-            {
-                break;
-            }
+    /// Report objective values for the batch returned by the last [`NSGAOptimizer::ask()`],
+    /// in the same order.
+    ///
+    /// Constraint violations are still computed using [`Meta::constraints()`].
+    /// The values are then ranked and the next population is selected
+    /// the same way [`NSGAOptimizer::optimize()`] does it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there isn't a value vector for every solution of the batch,
+    /// or a vector doesn't have a value for every objective.
+    pub fn tell(&mut self, values: Vec<Vec<f64>>) {
+        assert_eq!(
+            values.len(),
+            self.pending.len(),
+            "expected values for every solution of the last asked batch"
+        );
+
+        let objectives = self.meta.objectives().len();
+        assert!(
+            values.iter().all(|v| v.len() == objectives),
+            "expected a value for every objective"
+        );
+
+        let sols = std::mem::take(&mut self.pending);
+        let constraints = self.meta.constraints();
+        let violations: Vec<_> = sols.iter().map(|s| violation(constraints, s)).collect();
+
+        self.evaluations += values.len();
+
+        let pop = self.candidates(sols, values.into_iter().zip(violations).collect());
+        self.advance(pop);
+    }
 
-            // Check if we can already terminate
-            if parent_pop
-                .iter()
-                .map(|c| eval.can_terminate(iter, self.values(&c.sol)))
-                .any(|t| t)
-            {
-                break;
-            }
+    /// Returns true if any of the observers asked to stop after the last generation
+    pub fn stop_requested(&self) -> bool {
+        self.stop_requested
+    }
 
-            let mut child_pop: Vec<Candidate<S>> = Vec::with_capacity(pop_size);
+    /// Return the best solutions found so far
+    pub fn solutions(&self) -> impl Iterator<Item = &S> {
+        self.archive.iter().map(|s| &s.solution)
+    }
 
-            while child_pop.len() < pop_size {
-                let p1 = parent_pop.choose_mut(&mut rnd).unwrap().clone();
-                let p2 = parent_pop.choose_mut(&mut rnd).unwrap().clone();
-                let p3 = parent_pop.choose_mut(&mut rnd).unwrap().clone();
-                let p4 = parent_pop.choose_mut(&mut rnd).unwrap().clone();
+    // Produce and evaluate the initial population or the next generation
+    pub(crate) fn step(&mut self) {
+        let pop = if self.population.is_empty() {
+            self.initial_population()
+        } else {
+            self.offspring()
+        };
 
-                let mut c1 = self.tournament(p1, p2);
-                let mut c2 = self.tournament(p3, p4);
+        let pop = self.evaluate(pop);
+        let pop = self.replace_close(pop);
+        self.advance(pop);
+    }
 
-                if self.odds(crossover_odds) {
-                    c1.sol.crossover(&mut c2.sol);
-                };
+    // Check whether the optimization should stop after the current generation
+    pub(crate) fn termination(
+        &mut self,
+        eval: &mut dyn Evaluator,
+        started: Instant,
+    ) -> Option<Termination> {
+        // Check if any of the observers asked to stop
+        if self.stop_requested {
+            return Some(Termination::Observer);
+        }
 
-                if self.odds(mutation_odds) {
-                    c1.sol.mutate();
-                };
+        // Stop rather than lose the progress made since the last checkpoint
+        #[cfg(feature = "serde")]
+        if self.checkpoint.as_ref().is_some_and(|c| c.error.is_some()) {
+            return Some(Termination::Checkpoint);
+        }
 
-                if self.odds(mutation_odds) {
-                    c2.sol.mutate();
-                };
+        // Check if there's a good-enough solution already
+        let objectives = self.meta.objectives();
+        if self
+            .population
+            .iter()
+            .any(|c| good_enough(objectives, &c.values, c.violation))
+        {
+            return Some(Termination::GoodEnough);
+        }
 
-                c1.id = self.next_id();
-                c2.id = self.next_id();
+        // Check if we can already terminate
+        let progress = Progress::new(
+            self.directions(),
+            self.generation,
+            self.evaluations,
+            started.elapsed(),
+            self.population
+                .iter()
+                .map(|c| (c.values.as_slice(), c.violation)),
+        );
 
-                child_pop.push(c1);
-                child_pop.push(c2);
-            }
+        if eval.can_terminate(&progress) {
+            return Some(Termination::Evaluator);
+        }
 
-            parent_pop.extend(child_pop);
+        None
+    }
 
-            // Sort combined population
-            let sorted = self.sort(parent_pop);
-            let mut sorted_iter = sorted.into_iter().peekable();
+    // The least crowded members of the first front, along with their values
+    // so that they don't have to be evaluated again by other populations
+    pub(crate) fn emigrants(&self, n: usize) -> Vec<ParetoSolution<S>> {
+        let front: Vec<_> = self
+            .population
+            .iter()
+            .take_while(|c| c.front == 0)
+            .collect();
 
-            // Now select the next population
-            let mut next_pop: Vec<_> = Vec::with_capacity(pop_size);
-            let mut front = 0;
+        // Members of a front are sorted by ascending crowding distance
+        front
+            .iter()
+            .rev()
+            .take(n)
+            .map(|c| ParetoSolution {
+                solution: c.sol.clone(),
+                values: c.natural.clone(),
+                front: 0,
+                violation: c.violation,
+            })
+            .collect()
+    }
 
-            while next_pop.len() != pop_size {
-                let mut front_items: Vec<_> = sorted_iter
-                    .by_ref()
-                    .peeking_take_while(|i| i.front == front)
-                    .collect();
+    // Let solutions coming from other populations, already evaluated there,
+    // compete with the current population
+    pub(crate) fn immigrate(&mut self, sols: Vec<ParetoSolution<S>>) {
+        if sols.is_empty() || self.population.is_empty() {
+            return;
+        }
 
-                // Front fits entirely
-                if next_pop.len() + front_items.len() < next_pop.capacity() {
-                    next_pop.extend(front_items);
+        let (sols, vals) = sols
+            .into_iter()
+            .map(|p| (p.solution, (p.values, p.violation)))
+            .unzip();
 
-                    front += 1;
-                } else {
-                    front_items.sort_by(|a, b| b.distance.partial_cmp(&a.distance).unwrap());
+        let mut pop = std::mem::take(&mut self.population);
+        pop.extend(self.candidates(sols, vals));
 
-                    let rest: Vec<_> = front_items.drain(..(pop_size - next_pop.len())).collect();
+        self.population = self.survive(pop);
+    }
 
-                    next_pop.extend(rest);
-                }
-            }
+    pub(crate) fn archive_solutions(&self) -> impl Iterator<Item = &ParetoSolution<S>> {
+        self.archive.iter()
+    }
 
-            parent_pop = next_pop;
-        }
+    pub(crate) fn population_is_empty(&self) -> bool {
+        self.population.is_empty()
+    }
 
-        let best = std::mem::take(&mut self.best_solutions);
-        best.into_iter().map(|s| s.1)
+    pub(crate) fn directions(&self) -> Vec<Direction> {
+        directions(self.meta.objectives())
     }
 
-    fn next_id(&mut self) -> SolutionId {
-        self.last_id += 1;
-        self.last_id
+    pub(crate) fn generation(&self) -> usize {
+        self.generation
     }
 
-    fn odds(&self, ratio: &Ratio) -> bool {
-        thread_rng().gen_ratio(ratio.0, ratio.1)
+    pub(crate) fn population_size(&self) -> usize {
+        self.meta.population_size()
     }
 
-    fn tournament(&self, p1: Candidate<S>, p2: Candidate<S>) -> Candidate<S> {
-        let mut rnd = rand::thread_rng();
+    pub(crate) fn evaluations(&self) -> usize {
+        self.evaluations
+    }
 
-        if p1.front < p2.front {
-            p1
-        } else if p2.front < p1.front {
-            p2
-        } else if p1.distance > p2.distance {
-            p1
-        } else if p2.distance > p1.distance {
-            p2
-        } else {
-            vec![p1, p2].remove(rnd.gen_range(0..=1))
+    fn initial_population(&mut self) -> Vec<S> {
+        let pop_size = self.meta.population_size();
+
+        let mut pop = std::mem::take(&mut self.warm_start);
+        pop.truncate(pop_size);
+
+        while pop.len() < pop_size {
+            pop.push(self.meta.random_solution(&mut self.rng));
         }
+
+        self.replace_duplicates(pop)
     }
 
-    #[allow(clippy::needless_range_loop)]
-    fn sort(&self, pop: Vec<Candidate<S>>) -> Vec<Candidate<S>> {
-        let mut dominates: HashMap<SolutionId, HashSet<SolutionId>> = HashMap::new();
-        let mut dominated_by: HashMap<SolutionId, usize> = HashMap::new();
-
-        let ids: Vec<_> = pop.iter().map(|c| c.id).collect();
-        let mut sols: HashMap<SolutionId, S> = pop.into_iter().map(|c| (c.id, c.sol)).collect();
-
-        let mut fronts: Vec<HashSet<SolutionId>> = vec![HashSet::new()];
-
-        // Stage 1
-        for i in 0..ids.len() {
-            let i_id = ids[i];
-
-            for j in i + 1..ids.len() {
-                let j_id = ids[j];
-                let sol_i = &sols[&i_id];
-                let sol_j = &sols[&j_id];
-
-                let r = if self.dominates(sol_i, sol_j) {
-                    Some((i_id, j_id))
-                } else if self.dominates(sol_j, sol_i) {
-                    Some((j_id, i_id))
-                } else {
-                    None
-                };
-
-                if let Some((d, dby)) = r {
-                    dominates.entry(d).or_insert_with(HashSet::new).insert(dby);
-                    *dominated_by.entry(dby).or_insert(0) += 1;
+    // Produce the offspring of the current population
+    fn offspring(&mut self) -> Vec<S> {
+        let pop_size = self.meta.population_size();
+        let crossover_odds = self.meta.crossover_odds();
+        let mutation_odds = self.meta.mutation_odds();
+
+        let size = self.steady_state.unwrap_or(pop_size);
+
+        let mut child_pop: Vec<S> = Vec::with_capacity(size + 1);
+        let population: Vec<_> = self.population.iter().map(Candidate::individual).collect();
+
+        while child_pop.len() < size {
+            let p1 = self.selection.select(&population, &mut self.rng);
+            let p2 = self.selection.select(&population, &mut self.rng);
+
+            let mut c1 = self.population[p1].sol.clone();
+            let mut c2 = self.population[p2].sol.clone();
+
+            if self.rng.gen_ratio(crossover_odds.0, crossover_odds.1) {
+                c1.crossover(&mut c2, &mut self.rng);
+            };
+
+            if self.rng.gen_ratio(mutation_odds.0, mutation_odds.1) {
+                c1.mutate(&mut self.rng);
+            };
+
+            if self.rng.gen_ratio(mutation_odds.0, mutation_odds.1) {
+                c2.mutate(&mut self.rng);
+            };
+
+            child_pop.push(c1);
+            child_pop.push(c2);
+        }
+
+        if self.steady_state.is_some() {
+            child_pop.truncate(size);
+        }
+
+        self.replace_duplicates(child_pop)
+    }
+
+    // Replace solutions duplicating the population members or each other,
+    // dropping the ones still duplicated after the last attempt
+    fn replace_duplicates(&mut self, sols: Vec<S>) -> Vec<S> {
+        let mut hashes = HashSet::new();
+
+        if let Some(Duplicates::Hash(hash)) = &self.duplicates {
+            hashes.extend(self.population.iter().map(|c| hash(&c.sol)));
+        }
+
+        let mut unique: Vec<S> = Vec::with_capacity(sols.len());
+
+        for mut sol in sols {
+            let mut attempt = 0;
+
+            let duplicate = loop {
+                let others = self.population.iter().map(|c| &c.sol).chain(&unique);
+                let duplicate =
+                    matches!(&self.duplicates, Some(d) if d.contains(&sol, others, &hashes));
+
+                if !duplicate || attempt > MUTATION_ATTEMPTS {
+                    break duplicate;
                 }
+
+                sol = self.replacement(sol, attempt);
+                attempt += 1;
+            };
+
+            // Even the random solution may be a duplicate, e.g. of a small search space
+            if duplicate {
+                continue;
             }
 
-            if dominated_by.get(&i_id).is_none() {
-                fronts[0].insert(i_id);
+            if let Some(Duplicates::Hash(hash)) = &self.duplicates {
+                hashes.insert(hash(&sol));
             }
+
+            unique.push(sol);
         }
 
-        // Stage2
-        let mut i = 0;
-        while !fronts[i].is_empty() {
-            let mut new_front = HashSet::new();
-
-            for id in fronts[i].iter() {
-                if let Some(set) = dominates.get(id) {
-                    for dominated_id in set.iter() {
-                        dominated_by.entry(*dominated_id).and_modify(|v| {
-                            if v > &mut 0 {
-                                *v -= 1
-                            }
-                        });
-
-                        match dominated_by.get(dominated_id) {
-                            None | Some(0) => {
-                                if !new_front.contains(dominated_id) {
-                                    new_front.insert(*dominated_id);
-                                }
-                            }
-                            _ => (),
-                        }
-                    }
-                }
+        unique
+    }
+
+    // Replace evaluated candidates with objective values close to the ones
+    // of the population members or each other
+    fn replace_close(&mut self, mut pop: Vec<Candidate<S>>) -> Vec<Candidate<S>> {
+        let tolerance = match self.duplicates {
+            Some(Duplicates::Objectives { tolerance }) => tolerance,
+            _ => return pop,
+        };
+
+        for attempt in 0.. {
+            let close: Vec<usize> = (0..pop.len())
+                .filter(|&i| {
+                    self.population
+                        .iter()
+                        .chain(&pop[..i])
+                        .any(|c| close(&c.values, &pop[i].values, tolerance))
+                })
+                .collect();
+
+            if close.is_empty() {
+                break;
             }
 
-            i += 1;
-            fronts.push(new_front);
-        }
+            // Even the random solutions may be close, drop them
+            if attempt > MUTATION_ATTEMPTS {
+                for i in close.into_iter().rev() {
+                    pop.remove(i);
+                }
 
-        let mut flat_fronts: Vec<Candidate<S>> = Vec::with_capacity(fronts.len());
-        for (fidx, f) in fronts.into_iter().enumerate() {
-            for id in f {
-                let sol = sols.remove(&id).unwrap();
+                break;
+            }
 
-                flat_fronts.push(Candidate {
-                    id,
-                    sol,
-                    front: fidx,
-                    distance: 0.0,
-                });
+            let sols = close
+                .iter()
+                .map(|&i| self.replacement(pop[i].sol.clone(), attempt))
+                .collect();
+
+            for (i, c) in close.into_iter().zip(self.evaluate(sols)) {
+                pop[i] = c;
             }
         }
 
-        let mut fronts = flat_fronts;
-        debug_assert!(!fronts.is_empty());
+        pop
+    }
 
-        // Crowding distance
-        let fronts_len = fronts.len();
+    fn replacement(&mut self, mut sol: S, attempt: usize) -> S {
+        if attempt < MUTATION_ATTEMPTS {
+            sol.mutate(&mut self.rng);
+            sol
+        } else {
+            self.meta.random_solution(&mut self.rng)
+        }
+    }
 
-        for obj in self.meta.objectives() {
-            // Sort by objective
-            fronts.sort_by(|a, b| {
-                let a_obj = self.value(&a.sol, obj);
-                let b_obj = self.value(&b.sol, obj);
+    // Merge evaluated offspring into the population, select the next population
+    // and archive its best candidates
+    fn advance(&mut self, child_pop: Vec<Candidate<S>>) {
+        if self.population.is_empty() {
+            self.population = self.sort(child_pop);
+        } else if self.steady_state.is_some() {
+            for c in child_pop {
+                self.insert(c);
+            }
 
-                a_obj.partial_cmp(&b_obj).unwrap()
-            });
+            self.generation += 1;
+        } else {
+            let mut pop = std::mem::take(&mut self.population);
+            pop.extend(child_pop);
 
-            let min = self.value(&fronts[0].sol, obj);
-            let max = self.value(&fronts[fronts_len - 1].sol, obj);
+            self.population = self.survive(pop);
+            self.generation += 1;
+        }
 
-            let mut diff = (max - min) as f64;
-            if diff == 0. {
-                diff = 1.
-            }
+        // Keep copies of the best candidates in the archive
+        for c in self.population.iter().take_while(|c| c.front == 0) {
+            self.archive
+                .insert_with_violation(c.sol.clone(), c.values.clone(), c.violation);
+        }
+
+        self.observe();
 
-            fronts[0].distance = f64::MAX;
-            fronts[fronts_len - 1].distance = f64::MAX;
+        #[cfg(feature = "serde")]
+        if let Some(c) = &self.checkpoint {
+            if self.generation % c.every == 0 {
+                let error = (c.save)(self, &c.path).err();
 
-            for i in 2..fronts_len - 2 {
-                if fronts[i].distance != f64::MAX {
-                    fronts[i].distance += (obj.value(&fronts[i + 1].sol)
-                        - obj.value(&fronts[i - 1].sol))
-                    .abs() as f64
-                        / diff;
+                if let Some(c) = self.checkpoint.as_mut() {
+                    c.error = error;
                 }
             }
         }
+    }
+
+    fn observe(&mut self) {
+        let generation = Generation {
+            index: self.generation,
+            population: self.population.iter().map(Candidate::individual).collect(),
+            evaluations: self.evaluations,
+        };
+
+        // Every observer sees every generation
+        let mut stop = false;
+        for o in self.observers.iter_mut() {
+            stop |= o.observe(&generation);
+        }
+
+        self.stop_requested = stop;
+    }
+
+    // Select the next population out of the combined parent and offspring ones
+    fn survive(&mut self, pop: Vec<Candidate<S>>) -> Vec<Candidate<S>> {
+        // Dropped duplicates may leave fewer candidates than the population size
+        let pop_size = self.meta.population_size().min(pop.len());
+
+        if let (Survival::ReferencePoints { divisions }, true) =
+            (&self.survival, self.reference_directions.is_empty())
+        {
+            self.reference_directions =
+                reference_directions(self.meta.objectives().len(), *divisions);
+        }
+
+        // Sort combined population
+        let sorted = self.sort(pop);
+        let mut sorted_iter = sorted.into_iter().peekable();
+
+        // Now select the next population
+        let mut next_pop: Vec<_> = Vec::with_capacity(pop_size);
+        let mut front = 0;
+
+        while next_pop.len() != pop_size {
+            let mut front_items: Vec<_> = sorted_iter
+                .by_ref()
+                .peeking_take_while(|i| i.front == front)
+                .collect();
+
+            // Front fits entirely
+            if next_pop.len() + front_items.len() <= pop_size {
+                next_pop.extend(front_items);
+
+                front += 1;
+            } else if self.reference_directions.is_empty() {
+                front_items.sort_by(|a, b| {
+                    b.distance
+                        .partial_cmp(&a.distance)
+                        .unwrap_or(Ordering::Equal)
+                });
+
+                let rest: Vec<_> = front_items.drain(..(pop_size - next_pop.len())).collect();
 
-        // First sort by front and then by distance
-        fronts.sort_by(|a, b| {
-            if a.front != b.front {
-                a.front.cmp(&b.front)
-            } else if a.distance != b.distance {
-                a.distance.partial_cmp(&b.distance).unwrap()
+                next_pop.extend(rest);
             } else {
-                Ordering::Equal
+                let picked = select_by_reference(
+                    &next_pop.iter().map(|c| &c.values).collect::<Vec<_>>(),
+                    &front_items.iter().map(|c| &c.values).collect::<Vec<_>>(),
+                    pop_size - next_pop.len(),
+                    &self.reference_directions,
+                    &mut self.rng,
+                );
+
+                let mut front_items: Vec<_> = front_items.into_iter().map(Some).collect();
+                next_pop.extend(picked.into_iter().filter_map(|i| front_items[i].take()));
             }
-        });
+        }
 
-        fronts
+        next_pop
     }
 
-    #[allow(clippy::borrowed_box)]
-    fn value(&self, s: &S, obj: &Box<dyn Objective<S> + 'a>) -> f64 {
-        self.meta
-            .constraints()
-            .iter()
-            .fold(obj.value(s), |acc, cons| cons.value(s, acc))
+    fn next_id(&mut self) -> SolutionId {
+        self.last_id += 1;
+        self.last_id
     }
 
-    fn values(&self, s: &S) -> Vec<f64> {
-        self.meta
-            .objectives()
+    // Compute objective values for freshly produced solutions.
+    // This is the only place where objectives are evaluated.
+    fn evaluate(&mut self, sols: Vec<S>) -> Vec<Candidate<S>> {
+        let objectives = self.meta.objectives();
+        let values = self.meta.values();
+        let constraints = self.meta.constraints();
+
+        #[cfg(feature = "parallel")]
+        let vals: Vec<_> = sols
+            .par_iter()
+            .map(|s| evaluate(objectives, values, constraints, s))
+            .collect();
+
+        #[cfg(not(feature = "parallel"))]
+        let vals: Vec<_> = sols
             .iter()
-            .map(|obj| self.value(s, obj))
+            .map(|s| evaluate(objectives, values, constraints, s))
+            .collect();
+
+        self.evaluations += sols.len();
+        self.candidates(sols, vals)
+    }
+
+    // Wrap evaluated solutions into candidates, `vals` being in natural units
+    fn candidates(&mut self, sols: Vec<S>, vals: Vec<(Vec<f64>, f64)>) -> Vec<Candidate<S>> {
+        let directions = directions(self.meta.objectives());
+
+        sols.into_iter()
+            .zip(vals)
+            .map(|(sol, (natural, violation))| Candidate {
+                id: self.next_id(),
+                sol,
+                values: oriented(&directions, &natural),
+                natural,
+                violation,
+                front: 0,
+                distance: 0.0,
+            })
             .collect()
     }
 
-    fn dominates(&self, s1: &S, s2: &S) -> bool {
-        let vals1 = self.values(s1);
-        let vals2 = self.values(s2);
+    fn sort(&self, pop: Vec<Candidate<S>>) -> Vec<Candidate<S>> {
+        let ranks = constrained_non_dominated_sort(
+            &pop.iter().map(|c| &c.values).collect::<Vec<_>>(),
+            &pop.iter().map(|c| c.violation).collect::<Vec<_>>(),
+        );
+
+        let mut fronts: Vec<Candidate<S>> = pop
+            .into_iter()
+            .zip(ranks)
+            .map(|(mut c, front)| {
+                c.front = front;
+                c.distance = 0.0;
+                c
+            })
+            .collect();
+        fronts.sort_by_key(|c| (c.front, c.id));
+        crowd(&mut fronts, 0);
+
+        fronts
+    }
+
+    // Steady-state insertion: find the front of the new candidate,
+    // move the members it dominates one front down, then drop the most crowded
+    // member of the last front
+    fn insert(&mut self, mut c: Candidate<S>) {
+        let dominates = |a: &Candidate<S>, b: &Candidate<S>| {
+            constrained_dominates(&a.values, a.violation, &b.values, b.violation)
+        };
+
+        let mut pop = std::mem::take(&mut self.population);
+        let fronts = pop.last().map_or(0, |l| l.front + 1);
 
-        let vals: Vec<_> = vals1.into_iter().zip(vals2).collect();
+        let front = (0..fronts)
+            .find(|f| !pop.iter().any(|p| p.front == *f && dominates(p, &c)))
+            .unwrap_or(fronts);
+
+        // Members moved to the next front push down the ones they dominate there
+        let mut moving: Vec<usize> = (0..pop.len())
+            .filter(|&i| pop[i].front == front && dominates(&c, &pop[i]))
+            .collect();
+        let mut f = front;
+
+        while !moving.is_empty() {
+            let next: Vec<usize> = (0..pop.len())
+                .filter(|&i| {
+                    pop[i].front == f + 1 && moving.iter().any(|&m| dominates(&pop[m], &pop[i]))
+                })
+                .collect();
+
+            for m in moving {
+                pop[m].front = f + 1;
+            }
 
-        vals.iter().all(|(v1, v2)| v1 <= v2) && vals.iter().any(|(v1, v2)| v1 < v2)
+            moving = next;
+            f += 1;
+        }
+
+        c.front = front;
+        pop.push(c);
+        pop.sort_by_key(|c| (c.front, c.id));
+        crowd(&mut pop, front);
+
+        // The last front is sorted by ascending crowding distance
+        let last = pop.last().unwrap().front;
+        let worst = pop.iter().position(|c| c.front == last).unwrap();
+
+        pop.remove(worst);
+        pop.sort_by_key(|c| (c.front, c.id));
+        crowd(&mut pop, last);
+
+        self.population = pop;
     }
 }
+
+// Compute crowding distances within the fronts starting from `from`,
+// then sort the population by front and by distance.
+// Members of every front must be adjacent.
+fn crowd<S: Solution>(pop: &mut [Candidate<S>], from: usize) {
+    for front in pop.chunk_by_mut(|a, b| a.front == b.front) {
+        if front[0].front < from {
+            continue;
+        }
+
+        let distances = crowding_distance(&front.iter().map(|c| &c.values).collect::<Vec<_>>());
+
+        for (c, distance) in front.iter_mut().zip(distances) {
+            c.distance = distance;
+        }
+    }
+
+    // First sort by front and then by distance
+    pop.sort_by(|a, b| {
+        if a.front != b.front {
+            a.front.cmp(&b.front)
+        } else if a.distance != b.distance {
+            a.distance
+                .partial_cmp(&b.distance)
+                .unwrap_or(Ordering::Equal)
+        } else {
+            Ordering::Equal
+        }
+    });
+}
+
+pub(crate) type Objectives<'a, S> = [Box<dyn Objective<S> + 'a>];
+pub(crate) type Constraints<'a, S> = [Box<dyn Constraint<S> + 'a>];
+
+// Return objective values, as returned by the objectives or all at once
+// by `values` if provided, along with the total constraint violation
+pub(crate) fn evaluate<S: Solution>(
+    objectives: &Objectives<S>,
+    values: Option<&Values<S>>,
+    constraints: &Constraints<S>,
+    s: &S,
+) -> (Vec<f64>, f64) {
+    let values = match values {
+        Some(values) => values(s),
+        None => objectives.iter().map(|obj| obj.value(s)).collect(),
+    };
+
+    (values, violation(constraints, s))
+}
+
+// Returns true if a feasible solution is good enough in every objective,
+// `values` being oriented for minimization
+pub(crate) fn good_enough<S: Solution>(
+    objectives: &Objectives<S>,
+    values: &[f64],
+    violation: f64,
+) -> bool {
+    violation <= 0.
+        && objectives.iter().zip(values).all(|(obj, val)| {
+            obj.good_enough(match obj.direction() {
+                Direction::Minimize => *val,
+                Direction::Maximize => -val,
+            })
+        })
+}
+
+pub(crate) fn directions<S: Solution>(objectives: &Objectives<S>) -> Vec<Direction> {
+    objectives.iter().map(|obj| obj.direction()).collect()
+}
+
+// Negate the values of maximized objectives: everything is minimized internally.
+// Turns natural values into minimized ones and back.
+pub(crate) fn oriented(directions: &[Direction], values: &[f64]) -> Vec<f64> {
+    values
+        .iter()
+        .enumerate()
+        .map(|(i, v)| match directions.get(i) {
+            Some(Direction::Maximize) => -v,
+            _ => *v,
+        })
+        .collect()
+}
+
+fn violation<S: Solution>(constraints: &Constraints<S>, s: &S) -> f64 {
+    constraints
+        .iter()
+        .map(|cons| cons.violation(s).max(0.))
+        .sum()
+}
@@ -0,0 +1,119 @@
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+
+use rand_chacha::ChaCha8Rng;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use super::{Candidate, NSGAOptimizer, SolutionId};
+use crate::{Meta, ParetoArchive, Solution};
+
+// Everything needed to continue an interrupted optimization
+#[derive(Serialize, Deserialize)]
+#[serde(bound = "S: Serialize + DeserializeOwned")]
+struct State<S: Solution> {
+    population: Vec<Candidate<S>>,
+    pending: Vec<S>,
+    last_id: SolutionId,
+    archive: ParetoArchive<S>,
+    generation: usize,
+    evaluations: usize,
+    rng: ChaCha8Rng,
+}
+
+pub(super) struct Checkpoint<'a, S: Solution> {
+    pub(super) path: PathBuf,
+    pub(super) every: usize,
+    pub(super) save: fn(&NSGAOptimizer<'a, S>, &Path) -> io::Result<()>,
+    pub(super) error: Option<io::Error>,
+}
+
+impl<'a, S> NSGAOptimizer<'a, S>
+where
+    S: Solution + Serialize + DeserializeOwned,
+{
+    /// Save the optimizer state to a file.
+    ///
+    /// The state includes the current population, the archive of the best solutions,
+    /// the generation counter and the random number generator state,
+    /// so that [`NSGAOptimizer::resume()`] can continue the optimization exactly
+    /// where it stopped.
+    ///
+    /// The state is written to a temporary file next to `path` first,
+    /// which then replaces `path`, so a crash while saving keeps the previous state.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let state = State {
+            population: self.population.clone(),
+            pending: self.pending.clone(),
+            last_id: self.last_id,
+            archive: self.archive.clone(),
+            generation: self.generation,
+            evaluations: self.evaluations,
+            rng: self.rng.clone(),
+        };
+
+        let path = path.as_ref();
+        let mut name = path
+            .file_name()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "not a file path"))?
+            .to_os_string();
+        name.push(".tmp");
+        let tmp = path.with_file_name(name);
+
+        let mut writer = BufWriter::new(File::create(&tmp)?);
+        bincode::serialize_into(&mut writer, &state).map_err(io::Error::other)?;
+        writer
+            .into_inner()
+            .map_err(|e| e.into_error())?
+            .sync_all()?;
+
+        fs::rename(&tmp, path)
+    }
+
+    /// Instantiate an optimizer with a given meta params
+    /// and the state previously saved with [`NSGAOptimizer::save()`].
+    ///
+    /// Other settings, like the survival strategy or observers,
+    /// are not part of the state and should be set again.
+    /// Neither is the evaluator state: stateful evaluators, like
+    /// [`crate::DefaultEvaluator`] or [`crate::TimeBudget`],
+    /// start counting from scratch after resuming.
+    pub fn resume(meta: impl Meta<'a, S> + 'a, path: impl AsRef<Path>) -> io::Result<Self> {
+        let reader = BufReader::new(File::open(path)?);
+        let state: State<S> = bincode::deserialize_from(reader)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        let mut optimizer = NSGAOptimizer::new(meta);
+        optimizer.population = state.population;
+        optimizer.pending = state.pending;
+        optimizer.last_id = state.last_id;
+        optimizer.archive = state.archive;
+        optimizer.generation = state.generation;
+        optimizer.evaluations = state.evaluations;
+        optimizer.rng = state.rng;
+
+        Ok(optimizer)
+    }
+
+    /// Save the optimizer state to `path` after every `every` generations.
+    ///
+    /// If the state cannot be saved, the optimization stops with
+    /// [`crate::Termination::Checkpoint`] and the error is available
+    /// from [`NSGAOptimizer::checkpoint_error()`].
+    pub fn checkpoint(mut self, path: impl Into<PathBuf>, every: usize) -> Self {
+        self.checkpoint = Some(Checkpoint {
+            path: path.into(),
+            every: every.max(1),
+            save: |optimizer, path| optimizer.save(path),
+            error: None,
+        });
+
+        self
+    }
+
+    /// The error of the last failed checkpoint, if any
+    pub fn checkpoint_error(&self) -> Option<&io::Error> {
+        self.checkpoint.as_ref().and_then(|c| c.error.as_ref())
+    }
+}
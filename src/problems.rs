@@ -0,0 +1,339 @@
+//! Standard benchmark problems: the [ZDT](https://doi.org/10.1162/106365600568202),
+//! [DTLZ](https://doi.org/10.1007/1-84628-137-7_6) and
+//! [WFG](https://doi.org/10.1109/TEVC.2005.861417) suites.
+//!
+//! Every problem is a ready-made [`Meta`] implementation along with a generator
+//! of its true Pareto front, so optimizers and operators can be validated
+//! against the literature:
+//! ```
+//! use nsga::metrics::inverted_generational_distance;
+//! use nsga::problems::zdt1;
+//! use nsga::{DefaultEvaluator, NSGAOptimizer};
+//!
+//! let problem = zdt1(30).population_size(50);
+//! let front = problem.front(100);
+//!
+//! let result = NSGAOptimizer::new(problem)
+//!     .seed(0)
+//!     .optimize(Box::new(DefaultEvaluator::new(10)));
+//!
+//! assert!(inverted_generational_distance(&result, &front) < 1.);
+//! ```
+//!
+//! Real-valued problems use [`RealVector`] solutions with simulated binary crossover
+//! and polynomial mutation, ZDT5 uses [`BinaryVector`] solutions.
+
+use rand::{Rng, RngCore};
+use std::sync::Arc;
+
+use crate::sorting::non_dominated_sort;
+use crate::survival::reference_directions;
+use crate::{Constraint, Meta, Objective, Ratio, Solution, Values};
+
+mod dtlz;
+mod wfg;
+mod zdt;
+
+pub use dtlz::{dtlz1, dtlz2, dtlz3, dtlz4, dtlz5, dtlz6, dtlz7};
+pub use wfg::{wfg1, wfg2, wfg3, wfg4, wfg5, wfg6, wfg7, wfg8, wfg9};
+pub use zdt::{zdt1, zdt2, zdt3, zdt4, zdt5, zdt6};
+
+// Distribution indices of the simulated binary crossover and the polynomial mutation
+const CROSSOVER_ETA: f64 = 20.;
+const MUTATION_ETA: f64 = 20.;
+
+/// A vector of real variables, each one within its bounds
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RealVector {
+    pub x: Vec<f64>,
+    /// Lower and upper bounds of every variable
+    pub bounds: Vec<(f64, f64)>,
+}
+
+impl Solution for RealVector {
+    // Bounded SBX crossover of every variable with the odds of 1/2
+    // https://doi.org/10.1007/978-3-540-24854-5_64
+    fn crossover(&mut self, other: &mut Self, rng: &mut dyn RngCore) {
+        for (i, (lower, upper)) in self.bounds.iter().copied().enumerate() {
+            let (x1, x2) = (self.x[i], other.x[i]);
+
+            if rng.gen_bool(0.5) || (x1 - x2).abs() <= 1e-14 {
+                continue;
+            }
+
+            let (y1, y2) = (x1.min(x2), x1.max(x2));
+            let u: f64 = rng.gen_range(0.0..1.0);
+
+            let spread = |beta: f64| {
+                let alpha = 2. - beta.powf(-(CROSSOVER_ETA + 1.));
+
+                if u <= 1. / alpha {
+                    (u * alpha).powf(1. / (CROSSOVER_ETA + 1.))
+                } else {
+                    (1. / (2. - u * alpha)).powf(1. / (CROSSOVER_ETA + 1.))
+                }
+            };
+
+            let beta1 = spread(1. + 2. * (y1 - lower) / (y2 - y1));
+            let beta2 = spread(1. + 2. * (upper - y2) / (y2 - y1));
+
+            let c1 = (0.5 * (y1 + y2 - beta1 * (y2 - y1))).clamp(lower, upper);
+            let c2 = (0.5 * (y1 + y2 + beta2 * (y2 - y1))).clamp(lower, upper);
+
+            if rng.gen_bool(0.5) {
+                (self.x[i], other.x[i]) = (c2, c1);
+            } else {
+                (self.x[i], other.x[i]) = (c1, c2);
+            }
+        }
+    }
+
+    // Polynomial mutation of every variable with the odds of 1/n
+    // https://doi.org/10.1007/978-3-540-24854-5_64
+    fn mutate(&mut self, rng: &mut dyn RngCore) {
+        let odds = 1. / self.x.len() as f64;
+
+        for (x, (lower, upper)) in self.x.iter_mut().zip(self.bounds.iter().copied()) {
+            if !rng.gen_bool(odds) || upper <= lower {
+                continue;
+            }
+
+            let u: f64 = rng.gen_range(0.0..1.0);
+            let power = 1. / (MUTATION_ETA + 1.);
+
+            let delta = if u < 0.5 {
+                let xy = 1. - (*x - lower) / (upper - lower);
+                let val = 2. * u + (1. - 2. * u) * xy.powf(MUTATION_ETA + 1.);
+                val.powf(power) - 1.
+            } else {
+                let xy = 1. - (upper - *x) / (upper - lower);
+                let val = 2. * (1. - u) + 2. * (u - 0.5) * xy.powf(MUTATION_ETA + 1.);
+                1. - val.powf(power)
+            };
+
+            *x = (*x + delta * (upper - lower)).clamp(lower, upper);
+        }
+    }
+}
+
+/// A vector of bits
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BinaryVector {
+    pub bits: Vec<bool>,
+}
+
+impl Solution for BinaryVector {
+    // Single-point crossover
+    fn crossover(&mut self, other: &mut Self, rng: &mut dyn RngCore) {
+        let point = rng.gen_range(0..=self.bits.len());
+
+        for (b1, b2) in self.bits[point..].iter_mut().zip(&mut other.bits[point..]) {
+            std::mem::swap(b1, b2);
+        }
+    }
+
+    // Flip every bit with the odds of 1/n
+    fn mutate(&mut self, rng: &mut dyn RngCore) {
+        let odds = 1. / self.bits.len() as f64;
+
+        for b in self.bits.iter_mut() {
+            if rng.gen_bool(odds) {
+                *b = !*b;
+            }
+        }
+    }
+}
+
+type Generator<S> = dyn Fn(&mut dyn RngCore) -> S + Send + Sync;
+type FrontFn = dyn Fn(usize) -> Vec<Vec<f64>> + Send + Sync;
+
+// A single objective out of all the values computed at once.
+// Optimizers use the whole function through `Meta::values()` instead
+struct Component<S> {
+    index: usize,
+    function: Arc<Values<S>>,
+}
+
+impl<S: Solution> Objective<S> for Component<S> {
+    fn value(&self, sol: &S) -> f64 {
+        (self.function)(sol)[self.index]
+    }
+}
+
+/// A benchmark problem, see the [module](self) docs.
+///
+/// It has a population of 100, crossover odds of 9/10 and mutation odds of 1,
+/// the operators decide which variables change.
+pub struct Problem<'a, S: Solution> {
+    name: String,
+    population_size: usize,
+    crossover_odds: &'a Ratio,
+    mutation_odds: &'a Ratio,
+    generator: Box<Generator<S>>,
+    function: Arc<Values<S>>,
+    objectives: Vec<Box<dyn Objective<S> + 'a>>,
+    constraints: Vec<Box<dyn Constraint<S> + 'a>>,
+    front: Box<FrontFn>,
+}
+
+impl<'a, S: Solution + 'a> Problem<'a, S> {
+    pub(crate) fn new(
+        name: String,
+        objectives: usize,
+        function: impl Fn(&S) -> Vec<f64> + Send + Sync + 'static,
+        generator: impl Fn(&mut dyn RngCore) -> S + Send + Sync + 'static,
+        front: impl Fn(usize) -> Vec<Vec<f64>> + Send + Sync + 'static,
+    ) -> Self {
+        let function: Arc<Values<S>> = Arc::new(function);
+
+        Problem {
+            name,
+            population_size: 100,
+            crossover_odds: &Ratio(9, 10),
+            mutation_odds: &Ratio(1, 1),
+            generator: Box::new(generator),
+            objectives: (0..objectives)
+                .map(|index| {
+                    Box::new(Component {
+                        index,
+                        function: function.clone(),
+                    }) as Box<dyn Objective<S> + 'a>
+                })
+                .collect(),
+            function,
+            constraints: Vec::new(),
+            front: Box::new(front),
+        }
+    }
+
+    /// The name of the problem along with its dimensions, e.g. "DTLZ2(3, 12)"
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Set the population size
+    pub fn population_size(mut self, size: usize) -> Self {
+        self.population_size = size;
+        self
+    }
+
+    /// Set the crossover odds
+    pub fn crossover_odds(mut self, odds: &'a Ratio) -> Self {
+        self.crossover_odds = odds;
+        self
+    }
+
+    /// Set the mutation odds
+    pub fn mutation_odds(mut self, odds: &'a Ratio) -> Self {
+        self.mutation_odds = odds;
+        self
+    }
+
+    /// Generate about `points` objective vectors of the true Pareto front.
+    ///
+    /// Fronts are sampled evenly, disconnected ones may have fewer points
+    /// and discrete ones have as many points as they have.
+    pub fn front(&self, points: usize) -> Vec<Vec<f64>> {
+        (self.front)(points)
+    }
+}
+
+impl<'a, S: Solution> Meta<'a, S> for Problem<'a, S> {
+    fn population_size(&self) -> usize {
+        self.population_size
+    }
+
+    fn crossover_odds(&self) -> &'a Ratio {
+        self.crossover_odds
+    }
+
+    fn mutation_odds(&self) -> &'a Ratio {
+        self.mutation_odds
+    }
+
+    fn random_solution(&mut self, rng: &mut dyn RngCore) -> S {
+        (self.generator)(rng)
+    }
+
+    fn objectives(&self) -> &Vec<Box<dyn Objective<S> + 'a>> {
+        &self.objectives
+    }
+
+    fn values(&self) -> Option<&Values<S>> {
+        Some(&*self.function)
+    }
+
+    fn constraints(&self) -> &Vec<Box<dyn Constraint<S> + 'a>> {
+        &self.constraints
+    }
+}
+
+// A random real vector within the bounds
+fn random_vector(bounds: &[(f64, f64)], rng: &mut dyn RngCore) -> RealVector {
+    RealVector {
+        x: bounds.iter().map(|(l, u)| rng.gen_range(*l..=*u)).collect(),
+        bounds: bounds.to_vec(),
+    }
+}
+
+// `points` evenly spaced values from `from` to `to`
+fn linspace(from: f64, to: f64, points: usize) -> Vec<f64> {
+    match points {
+        0 => Vec::new(),
+        1 => vec![from],
+        _ => (0..points)
+            .map(|i| from + (to - from) * i as f64 / (points - 1) as f64)
+            .collect(),
+    }
+}
+
+// Evenly spread points of the unit simplex, at least `points` of them
+fn simplex(objectives: usize, points: usize) -> Vec<Vec<f64>> {
+    // There are C(M + p - 1, p) directions for M objectives and p divisions
+    let count = |p: usize| (1..=p).fold(1., |c, i| c * (objectives + i - 1) as f64 / i as f64);
+    let divisions = (1..).find(|p| count(*p) + 0.5 >= points as f64).unwrap();
+
+    reference_directions(objectives, divisions)
+}
+
+// Evenly spread points of the unit sphere's positive orthant, at least `points` of them
+fn sphere(objectives: usize, points: usize) -> Vec<Vec<f64>> {
+    simplex(objectives, points)
+        .into_iter()
+        .map(|d| {
+            let norm = d.iter().map(|v| v * v).sum::<f64>().sqrt();
+            d.iter().map(|v| v / norm).collect()
+        })
+        .collect()
+}
+
+// A regular grid of about `points` points of the unit hypercube
+fn grid(dimensions: usize, points: usize) -> Vec<Vec<f64>> {
+    let per_dimension = (points as f64).powf(1. / dimensions as f64).ceil().max(2.) as usize;
+    let values = linspace(0., 1., per_dimension);
+
+    (0..dimensions).fold(vec![Vec::new()], |acc, _| {
+        acc.iter()
+            .flat_map(|p| {
+                values.iter().map(move |v| {
+                    let mut p = p.clone();
+                    p.push(*v);
+                    p
+                })
+            })
+            .collect()
+    })
+}
+
+// Only the non-dominated vectors
+fn non_dominated(values: Vec<Vec<f64>>) -> Vec<Vec<f64>> {
+    let fronts = non_dominated_sort(&values);
+
+    values
+        .into_iter()
+        .zip(fronts)
+        .filter_map(|(v, front)| (front == 0).then_some(v))
+        .collect()
+}
@@ -0,0 +1,248 @@
+use std::f64::consts::PI;
+
+use super::{grid, linspace, non_dominated, random_vector, simplex, sphere, Problem, RealVector};
+
+// The exponent DTLZ4 maps the position variables with
+const DTLZ4_ALPHA: f64 = 100.;
+
+// A problem of `objectives` objectives and `variables` variables in [0, 1],
+// the last `variables - objectives + 1` of them being the distance variables
+fn dtlz(
+    name: &str,
+    objectives: usize,
+    variables: usize,
+    function: impl Fn(&[f64], &[f64]) -> Vec<f64> + Send + Sync + 'static,
+    front: impl Fn(usize) -> Vec<Vec<f64>> + Send + Sync + 'static,
+) -> Problem<'static, RealVector> {
+    assert!(
+        objectives >= 2,
+        "DTLZ problems need at least two objectives"
+    );
+    assert!(
+        variables >= objectives,
+        "DTLZ problems need at least as many variables as objectives"
+    );
+
+    let bounds = vec![(0., 1.); variables];
+
+    Problem::new(
+        format!("{}({}, {})", name, objectives, variables),
+        objectives,
+        move |s: &RealVector| {
+            let (position, distance) = s.x.split_at(objectives - 1);
+            function(position, distance)
+        },
+        move |rng| random_vector(&bounds, rng),
+        front,
+    )
+}
+
+// The Rastrigin-like distance function of DTLZ1 and DTLZ3
+fn multimodal(distance: &[f64]) -> f64 {
+    100. * (distance.len() as f64
+        + distance
+            .iter()
+            .map(|x| (x - 0.5).powi(2) - (20. * PI * (x - 0.5)).cos())
+            .sum::<f64>())
+}
+
+fn squares(distance: &[f64]) -> f64 {
+    distance.iter().map(|x| (x - 0.5).powi(2)).sum()
+}
+
+// Objectives on a hyperplane scaled by 1 + g
+fn linear(position: &[f64], g: f64) -> Vec<f64> {
+    let m = position.len() + 1;
+
+    (0..m)
+        .map(|i| {
+            let product: f64 = position[..m - 1 - i].iter().product();
+            let last = if i > 0 { 1. - position[m - 1 - i] } else { 1. };
+
+            0.5 * product * last * (1. + g)
+        })
+        .collect()
+}
+
+// Objectives on a sphere of radius 1 + g, `angles` being in [0, 1]
+fn spherical(angles: &[f64], g: f64) -> Vec<f64> {
+    let m = angles.len() + 1;
+
+    (0..m)
+        .map(|i| {
+            let product: f64 = angles[..m - 1 - i]
+                .iter()
+                .map(|a| (a * PI / 2.).cos())
+                .product();
+            let last = if i > 0 {
+                (angles[m - 1 - i] * PI / 2.).sin()
+            } else {
+                1.
+            };
+
+            (1. + g) * product * last
+        })
+        .collect()
+}
+
+// Angles of DTLZ5 and DTLZ6: all but the first one tend to π/4 as g gets to 0
+fn degenerate(position: &[f64], g: f64) -> Vec<f64> {
+    position
+        .iter()
+        .enumerate()
+        .map(|(i, x)| {
+            if i == 0 {
+                *x
+            } else {
+                (1. + 2. * g * x) / (2. * (1. + g))
+            }
+        })
+        .collect()
+}
+
+// The degenerate curve of DTLZ5 and DTLZ6
+fn curve(objectives: usize) -> impl Fn(usize) -> Vec<Vec<f64>> + Send + Sync + 'static {
+    move |points| {
+        linspace(0., 1., points)
+            .into_iter()
+            .map(|x| {
+                let mut position = vec![0.5; objectives - 1];
+                position[0] = x;
+
+                spherical(&degenerate(&position, 0.), 0.)
+            })
+            .collect()
+    }
+}
+
+/// DTLZ1 with `objectives` objectives and `variables` variables in \[0, 1\],
+/// `objectives + 4` in the literature.
+/// The front is linear, with a lot of local fronts.
+pub fn dtlz1(objectives: usize, variables: usize) -> Problem<'static, RealVector> {
+    dtlz(
+        "DTLZ1",
+        objectives,
+        variables,
+        |position, distance| linear(position, multimodal(distance)),
+        move |points| {
+            simplex(objectives, points)
+                .into_iter()
+                .map(|p| p.iter().map(|v| v / 2.).collect())
+                .collect()
+        },
+    )
+}
+
+/// DTLZ2 with `objectives` objectives and `variables` variables in \[0, 1\],
+/// `objectives + 9` in the literature.
+/// The front is spherical.
+pub fn dtlz2(objectives: usize, variables: usize) -> Problem<'static, RealVector> {
+    dtlz(
+        "DTLZ2",
+        objectives,
+        variables,
+        |position, distance| spherical(position, squares(distance)),
+        move |points| sphere(objectives, points),
+    )
+}
+
+/// DTLZ3 with `objectives` objectives and `variables` variables in \[0, 1\],
+/// `objectives + 9` in the literature.
+/// The front is spherical, with a lot of local fronts.
+pub fn dtlz3(objectives: usize, variables: usize) -> Problem<'static, RealVector> {
+    dtlz(
+        "DTLZ3",
+        objectives,
+        variables,
+        |position, distance| spherical(position, multimodal(distance)),
+        move |points| sphere(objectives, points),
+    )
+}
+
+/// DTLZ4 with `objectives` objectives and `variables` variables in \[0, 1\],
+/// `objectives + 9` in the literature.
+/// The front is spherical, with solutions biased towards its edges.
+pub fn dtlz4(objectives: usize, variables: usize) -> Problem<'static, RealVector> {
+    dtlz(
+        "DTLZ4",
+        objectives,
+        variables,
+        |position, distance| {
+            let angles: Vec<f64> = position.iter().map(|x| x.powf(DTLZ4_ALPHA)).collect();
+            spherical(&angles, squares(distance))
+        },
+        move |points| sphere(objectives, points),
+    )
+}
+
+/// DTLZ5 with `objectives` objectives and `variables` variables in \[0, 1\],
+/// `objectives + 9` in the literature.
+/// The front is a curve.
+pub fn dtlz5(objectives: usize, variables: usize) -> Problem<'static, RealVector> {
+    dtlz(
+        "DTLZ5",
+        objectives,
+        variables,
+        |position, distance| {
+            let g = squares(distance);
+            spherical(&degenerate(position, g), g)
+        },
+        curve(objectives),
+    )
+}
+
+/// DTLZ6 with `objectives` objectives and `variables` variables in \[0, 1\],
+/// `objectives + 9` in the literature.
+/// The front is a curve, harder to converge to than the one of DTLZ5.
+pub fn dtlz6(objectives: usize, variables: usize) -> Problem<'static, RealVector> {
+    dtlz(
+        "DTLZ6",
+        objectives,
+        variables,
+        |position, distance| {
+            let g: f64 = distance.iter().map(|x| x.powf(0.1)).sum();
+            spherical(&degenerate(position, g), g)
+        },
+        curve(objectives),
+    )
+}
+
+/// DTLZ7 with `objectives` objectives and `variables` variables in \[0, 1\],
+/// `objectives + 19` in the literature.
+/// The front consists of `2^(objectives - 1)` disconnected parts.
+pub fn dtlz7(objectives: usize, variables: usize) -> Problem<'static, RealVector> {
+    let last = |position: &[f64], g: f64| {
+        let h: f64 = position
+            .iter()
+            .map(|f| f / (1. + g) * (1. + (3. * PI * f).sin()))
+            .sum();
+
+        (1. + g) * (position.len() as f64 + 1. - h)
+    };
+
+    dtlz(
+        "DTLZ7",
+        objectives,
+        variables,
+        move |position, distance| {
+            let g = 1. + 9. * distance.iter().sum::<f64>() / distance.len() as f64;
+
+            let mut values = position.to_vec();
+            values.push(last(position, g));
+            values
+        },
+        move |points| {
+            // The disconnected parts take only a fraction of the sampled area
+            let sampled = grid(objectives - 1, points * 4)
+                .into_iter()
+                .map(|mut p| {
+                    let f = last(&p, 1.);
+                    p.push(f);
+                    p
+                })
+                .collect();
+
+            non_dominated(sampled)
+        },
+    )
+}
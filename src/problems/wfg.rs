@@ -0,0 +1,472 @@
+use std::f64::consts::PI;
+
+use super::{grid, linspace, non_dominated, random_vector, sphere, Problem, RealVector};
+
+// Parameters of the b_param transformation of WFG7, WFG8 and WFG9
+const PARAM_A: f64 = 0.98 / 49.98;
+const PARAM_B: f64 = 0.02;
+const PARAM_C: f64 = 50.;
+
+type Shape = fn(&[f64], usize) -> f64;
+
+// A problem of `objectives` objectives with `k` position and `l` distance variables,
+// `transform` maps the normalized variables to the M underlying parameters
+// which are then mapped to the objectives by `shape`.
+// The position parameters of degenerate problems but the first one don't matter.
+fn wfg(
+    name: &str,
+    (objectives, k, l): (usize, usize, usize),
+    transform: impl Fn(Vec<f64>, usize, usize) -> Vec<f64> + Send + Sync + 'static,
+    shape: Shape,
+    degenerate: bool,
+    front: impl Fn(usize) -> Vec<Vec<f64>> + Send + Sync + 'static,
+) -> Problem<'static, RealVector> {
+    assert!(objectives >= 2, "WFG problems need at least two objectives");
+    assert!(
+        k > 0 && k % (objectives - 1) == 0,
+        "the number of position variables must be a multiple of objectives - 1"
+    );
+    assert!(l > 0, "WFG problems need at least one distance variable");
+
+    let bounds: Vec<(f64, f64)> = (1..=k + l).map(|i| (0., 2. * i as f64)).collect();
+
+    Problem::new(
+        format!("{}({}, {}, {})", name, objectives, k, l),
+        objectives,
+        move |s: &RealVector| {
+            let y = s.x.iter().zip(&s.bounds).map(|(z, b)| z / b.1).collect();
+            let t = transform(y, k, objectives);
+
+            let distance = t[objectives - 1];
+            let position: Vec<f64> = t[..objectives - 1]
+                .iter()
+                .enumerate()
+                .map(|(i, t)| {
+                    let a = if degenerate && i > 0 { 0. } else { 1. };
+                    distance.max(a) * (t - 0.5) + 0.5
+                })
+                .collect();
+
+            (1..=objectives)
+                .map(|m| distance + 2. * m as f64 * shape(&position, m))
+                .collect()
+        },
+        move |rng| random_vector(&bounds, rng),
+        front,
+    )
+}
+
+fn correct(v: f64) -> f64 {
+    v.clamp(0., 1.)
+}
+
+fn s_linear(y: f64, a: f64) -> f64 {
+    correct((y - a).abs() / ((a - y).floor() + a).abs())
+}
+
+fn s_decept(y: f64, a: f64, b: f64, c: f64) -> f64 {
+    let t1 = (y - a + b).floor() * (1. - c + (a - b) / b) / (a - b);
+    let t2 = (a + b - y).floor() * (1. - c + (1. - a - b) / b) / (1. - a - b);
+
+    correct(1. + ((y - a).abs() - b) * (t1 + t2 + 1. / b))
+}
+
+fn s_multi(y: f64, a: f64, b: f64, c: f64) -> f64 {
+    let t1 = (y - c).abs() / (2. * ((c - y).floor() + c));
+    let t2 = (4. * a + 2.) * PI * (0.5 - t1);
+
+    correct((1. + t2.cos() + 4. * b * t1 * t1) / (b + 2.))
+}
+
+fn b_flat(y: f64, a: f64, b: f64, c: f64) -> f64 {
+    let t1 = (y - b).floor().min(0.) * a * (b - y) / b;
+    let t2 = (c - y).floor().min(0.) * (1. - a) * (y - c) / (1. - c);
+
+    correct(a + t1 - t2)
+}
+
+fn b_poly(y: f64, alpha: f64) -> f64 {
+    correct(y.powf(alpha))
+}
+
+fn b_param(y: f64, u: f64, a: f64, b: f64, c: f64) -> f64 {
+    let v = a - (1. - 2. * u) * ((0.5 - u).floor() + a).abs();
+
+    correct(y.powf(b + (c - b) * v))
+}
+
+fn r_sum(y: &[f64], w: &[f64]) -> f64 {
+    let total: f64 = w.iter().sum();
+    correct(y.iter().zip(w).map(|(y, w)| y * w).sum::<f64>() / total)
+}
+
+fn r_nonsep(y: &[f64], a: usize) -> f64 {
+    let n = y.len();
+    let half = (a as f64 / 2.).ceil();
+
+    let sum: f64 = (0..n)
+        .map(|j| {
+            y[j] + (0..a.saturating_sub(1))
+                .map(|k| (y[j] - y[(1 + j + k) % n]).abs())
+                .sum::<f64>()
+        })
+        .sum();
+
+    correct(sum / (n as f64 / a as f64 * half * (1. + 2. * a as f64 - 2. * half)))
+}
+
+// Ranges of the M - 1 position groups followed by the distance one
+fn groups(n: usize, k: usize, objectives: usize) -> Vec<(usize, usize)> {
+    let size = k / (objectives - 1);
+
+    (0..objectives - 1)
+        .map(|i| (i * size, (i + 1) * size))
+        .chain(std::iter::once((k, n)))
+        .collect()
+}
+
+// The weighted sum reduction with every weight being 1
+fn sum_reduction(y: &[f64], k: usize, objectives: usize) -> Vec<f64> {
+    groups(y.len(), k, objectives)
+        .into_iter()
+        .map(|(from, to)| r_sum(&y[from..to], &vec![1.; to - from]))
+        .collect()
+}
+
+// The non-separable reduction of every group
+fn nonsep_reduction(y: &[f64], k: usize, objectives: usize) -> Vec<f64> {
+    groups(y.len(), k, objectives)
+        .into_iter()
+        .map(|(from, to)| r_nonsep(&y[from..to], to - from))
+        .collect()
+}
+
+// The shift of the distance variables shared by most of the problems
+fn shift_distance(mut y: Vec<f64>, k: usize) -> Vec<f64> {
+    for y in y[k..].iter_mut() {
+        *y = s_linear(*y, 0.35);
+    }
+
+    y
+}
+
+// Pairs of distance variables reduced into one, the first transformations of WFG2 and WFG3
+fn pair_distance(y: Vec<f64>, k: usize) -> Vec<f64> {
+    let mut y = shift_distance(y, k);
+    let pairs: Vec<f64> = y[k..].chunks(2).map(|p| r_nonsep(p, 2)).collect();
+
+    y.truncate(k);
+    y.extend(pairs);
+    y
+}
+
+// The dependency of variables on the average of `others`, the first transformations
+// of WFG7, WFG8 and WFG9
+fn depend(y: &[f64], i: usize, others: &[f64]) -> f64 {
+    let u = r_sum(others, &vec![1.; others.len()]);
+    b_param(y[i], u, PARAM_A, PARAM_B, PARAM_C)
+}
+
+fn linear(x: &[f64], m: usize) -> f64 {
+    let n = x.len() + 1;
+    let product: f64 = x[..n - m].iter().product();
+
+    if m > 1 {
+        product * (1. - x[n - m])
+    } else {
+        product
+    }
+}
+
+fn convex(x: &[f64], m: usize) -> f64 {
+    let n = x.len() + 1;
+    let product: f64 = x[..n - m]
+        .iter()
+        .map(|x| 1. - (x * PI / 2.).cos())
+        .product();
+
+    if m > 1 {
+        product * (1. - (x[n - m] * PI / 2.).sin())
+    } else {
+        product
+    }
+}
+
+fn concave(x: &[f64], m: usize) -> f64 {
+    let n = x.len() + 1;
+    let product: f64 = x[..n - m].iter().map(|x| (x * PI / 2.).sin()).product();
+
+    if m > 1 {
+        product * (x[n - m] * PI / 2.).cos()
+    } else {
+        product
+    }
+}
+
+// Convex, the last objective being mixed convex-concave with 5 segments
+fn mixed(x: &[f64], m: usize) -> f64 {
+    if m == x.len() + 1 {
+        let a = 5.;
+        1. - x[0] - (2. * a * PI * x[0] + PI / 2.).cos() / (2. * a * PI)
+    } else {
+        convex(x, m)
+    }
+}
+
+// Convex, the last objective being disconnected into 5 regions
+fn disconnected(x: &[f64], m: usize) -> f64 {
+    if m == x.len() + 1 {
+        1. - x[0] * (5. * x[0] * PI).cos().powi(2)
+    } else {
+        convex(x, m)
+    }
+}
+
+// Objectives of the optimal solutions for the given underlying position parameters
+fn optimum(x: &[f64], shape: Shape) -> Vec<f64> {
+    (1..=x.len() + 1)
+        .map(|m| 2. * m as f64 * shape(x, m))
+        .collect()
+}
+
+// The front of a shape, sampled over a grid of the position parameters
+fn sampled(shape: Shape, objectives: usize) -> impl Fn(usize) -> Vec<Vec<f64>> + Send + Sync {
+    move |points| {
+        non_dominated(
+            grid(objectives - 1, points)
+                .iter()
+                .map(|x| optimum(x, shape))
+                .collect(),
+        )
+    }
+}
+
+// The spherical front of WFG4 to WFG9
+fn spherical(objectives: usize) -> impl Fn(usize) -> Vec<Vec<f64>> + Send + Sync {
+    move |points| {
+        sphere(objectives, points)
+            .into_iter()
+            .map(|p| {
+                p.iter()
+                    .enumerate()
+                    .map(|(i, v)| 2. * (i + 1) as f64 * v)
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// WFG1 with `objectives` objectives, `k` position and `l` distance variables.
+/// `k` has to be a multiple of `objectives - 1`, `2 * (objectives - 1)` and 20
+/// are used in the literature. Variable `i` is in \[0, 2i\].
+///
+/// The front is convex and mixed, with a flat region biasing solutions.
+pub fn wfg1(objectives: usize, k: usize, l: usize) -> Problem<'static, RealVector> {
+    wfg(
+        "WFG1",
+        (objectives, k, l),
+        |y, k, objectives| {
+            let y: Vec<f64> = shift_distance(y, k)
+                .into_iter()
+                .enumerate()
+                .map(|(i, y)| {
+                    let y = if i < k { y } else { b_flat(y, 0.8, 0.75, 0.85) };
+                    b_poly(y, 0.02)
+                })
+                .collect();
+
+            let w: Vec<f64> = (1..=y.len()).map(|i| 2. * i as f64).collect();
+
+            groups(y.len(), k, objectives)
+                .into_iter()
+                .map(|(from, to)| r_sum(&y[from..to], &w[from..to]))
+                .collect()
+        },
+        mixed,
+        false,
+        sampled(mixed, objectives),
+    )
+}
+
+/// WFG2 with `objectives` objectives, `k` position and `l` distance variables,
+/// `l` has to be even, see [`wfg1()`].
+///
+/// The front is convex and disconnected.
+pub fn wfg2(objectives: usize, k: usize, l: usize) -> Problem<'static, RealVector> {
+    assert!(
+        l % 2 == 0,
+        "WFG2 needs an even number of distance variables"
+    );
+
+    wfg(
+        "WFG2",
+        (objectives, k, l),
+        |y, k, objectives| sum_reduction(&pair_distance(y, k), k, objectives),
+        disconnected,
+        false,
+        sampled(disconnected, objectives),
+    )
+}
+
+/// WFG3 with `objectives` objectives, `k` position and `l` distance variables,
+/// `l` has to be even, see [`wfg1()`].
+///
+/// The front is a line, degenerate for more than two objectives.
+pub fn wfg3(objectives: usize, k: usize, l: usize) -> Problem<'static, RealVector> {
+    assert!(
+        l % 2 == 0,
+        "WFG3 needs an even number of distance variables"
+    );
+
+    wfg(
+        "WFG3",
+        (objectives, k, l),
+        |y, k, objectives| sum_reduction(&pair_distance(y, k), k, objectives),
+        linear,
+        true,
+        move |points| {
+            linspace(0., 1., points)
+                .into_iter()
+                .map(|x| {
+                    let mut position = vec![0.5; objectives - 1];
+                    position[0] = x;
+
+                    optimum(&position, linear)
+                })
+                .collect()
+        },
+    )
+}
+
+/// WFG4 with `objectives` objectives, `k` position and `l` distance variables,
+/// see [`wfg1()`].
+///
+/// The front is concave, every variable is highly multimodal.
+pub fn wfg4(objectives: usize, k: usize, l: usize) -> Problem<'static, RealVector> {
+    wfg(
+        "WFG4",
+        (objectives, k, l),
+        |y, k, objectives| {
+            let y: Vec<f64> = y.iter().map(|y| s_multi(*y, 30., 10., 0.35)).collect();
+            sum_reduction(&y, k, objectives)
+        },
+        concave,
+        false,
+        spherical(objectives),
+    )
+}
+
+/// WFG5 with `objectives` objectives, `k` position and `l` distance variables,
+/// see [`wfg1()`].
+///
+/// The front is concave, every variable is deceptive.
+pub fn wfg5(objectives: usize, k: usize, l: usize) -> Problem<'static, RealVector> {
+    wfg(
+        "WFG5",
+        (objectives, k, l),
+        |y, k, objectives| {
+            let y: Vec<f64> = y.iter().map(|y| s_decept(*y, 0.35, 0.001, 0.05)).collect();
+            sum_reduction(&y, k, objectives)
+        },
+        concave,
+        false,
+        spherical(objectives),
+    )
+}
+
+/// WFG6 with `objectives` objectives, `k` position and `l` distance variables,
+/// see [`wfg1()`].
+///
+/// The front is concave, the variables are non-separable.
+pub fn wfg6(objectives: usize, k: usize, l: usize) -> Problem<'static, RealVector> {
+    wfg(
+        "WFG6",
+        (objectives, k, l),
+        |y, k, objectives| nonsep_reduction(&shift_distance(y, k), k, objectives),
+        concave,
+        false,
+        spherical(objectives),
+    )
+}
+
+/// WFG7 with `objectives` objectives, `k` position and `l` distance variables,
+/// see [`wfg1()`].
+///
+/// The front is concave, the position variables depend on the distance ones.
+pub fn wfg7(objectives: usize, k: usize, l: usize) -> Problem<'static, RealVector> {
+    wfg(
+        "WFG7",
+        (objectives, k, l),
+        |y, k, objectives| {
+            let y: Vec<f64> = (0..y.len())
+                .map(|i| {
+                    if i < k {
+                        depend(&y, i, &y[i + 1..])
+                    } else {
+                        y[i]
+                    }
+                })
+                .collect();
+
+            sum_reduction(&shift_distance(y, k), k, objectives)
+        },
+        concave,
+        false,
+        spherical(objectives),
+    )
+}
+
+/// WFG8 with `objectives` objectives, `k` position and `l` distance variables,
+/// see [`wfg1()`].
+///
+/// The front is concave, the distance variables depend on the position ones.
+pub fn wfg8(objectives: usize, k: usize, l: usize) -> Problem<'static, RealVector> {
+    wfg(
+        "WFG8",
+        (objectives, k, l),
+        |y, k, objectives| {
+            let y: Vec<f64> = (0..y.len())
+                .map(|i| if i < k { y[i] } else { depend(&y, i, &y[..i]) })
+                .collect();
+
+            sum_reduction(&shift_distance(y, k), k, objectives)
+        },
+        concave,
+        false,
+        spherical(objectives),
+    )
+}
+
+/// WFG9 with `objectives` objectives, `k` position and `l` distance variables,
+/// see [`wfg1()`].
+///
+/// The front is concave, the variables are non-separable, deceptive and multimodal.
+pub fn wfg9(objectives: usize, k: usize, l: usize) -> Problem<'static, RealVector> {
+    wfg(
+        "WFG9",
+        (objectives, k, l),
+        |y, k, objectives| {
+            let n = y.len();
+            let y: Vec<f64> = (0..n)
+                .map(|i| {
+                    let y = if i + 1 < n {
+                        depend(&y, i, &y[i + 1..])
+                    } else {
+                        y[i]
+                    };
+
+                    if i < k {
+                        s_decept(y, 0.35, 0.001, 0.05)
+                    } else {
+                        s_multi(y, 30., 95., 0.35)
+                    }
+                })
+                .collect();
+
+            nonsep_reduction(&y, k, objectives)
+        },
+        concave,
+        false,
+        spherical(objectives),
+    )
+}
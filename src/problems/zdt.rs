@@ -0,0 +1,208 @@
+use rand::Rng;
+use std::f64::consts::PI;
+
+use super::{linspace, random_vector, BinaryVector, Problem, RealVector};
+
+// Ranges of the first objective forming the disconnected front of ZDT3
+const ZDT3_REGIONS: [(f64, f64); 5] = [
+    (0., 0.0830015349),
+    (0.182228780, 0.2577623634),
+    (0.4093136748, 0.4538821041),
+    (0.6183967944, 0.6525117038),
+    (0.8233317983, 0.8518328654),
+];
+
+// The smallest first objective on the front of ZDT6
+const ZDT6_MIN: f64 = 0.2807753191;
+
+// Bits of the first variable and every following one of ZDT5
+const ZDT5_HEAD: usize = 30;
+const ZDT5_TAIL: usize = 5;
+
+// A two-objective problem with f1 = x1 and f2 = g * h(f1, g),
+// g being a function of all the variables but the first one
+fn zdt(
+    name: &str,
+    bounds: Vec<(f64, f64)>,
+    g: impl Fn(&[f64]) -> f64 + Send + Sync + 'static,
+    h: impl Fn(f64, f64) -> f64 + Send + Sync + 'static,
+    front: impl Fn(usize) -> Vec<Vec<f64>> + Send + Sync + 'static,
+) -> Problem<'static, RealVector> {
+    zdt_with(name, bounds, |x| x[0], g, h, front)
+}
+
+// A two-objective problem with f2 = g * h(f1, g)
+fn zdt_with(
+    name: &str,
+    bounds: Vec<(f64, f64)>,
+    f1: impl Fn(&[f64]) -> f64 + Send + Sync + 'static,
+    g: impl Fn(&[f64]) -> f64 + Send + Sync + 'static,
+    h: impl Fn(f64, f64) -> f64 + Send + Sync + 'static,
+    front: impl Fn(usize) -> Vec<Vec<f64>> + Send + Sync + 'static,
+) -> Problem<'static, RealVector> {
+    assert!(
+        bounds.len() >= 2,
+        "ZDT problems need at least two variables"
+    );
+
+    Problem::new(
+        format!("{}({})", name, bounds.len()),
+        2,
+        move |s: &RealVector| {
+            let f1 = f1(&s.x);
+            let g = g(&s.x[1..]);
+
+            vec![f1, g * h(f1, g)]
+        },
+        move |rng| random_vector(&bounds, rng),
+        front,
+    )
+}
+
+fn unit(variables: usize) -> Vec<(f64, f64)> {
+    vec![(0., 1.); variables]
+}
+
+// 1 + 9 times the average of the variables
+fn average(x: &[f64]) -> f64 {
+    1. + 9. * x.iter().sum::<f64>() / x.len() as f64
+}
+
+// The front with f2 = shape(f1) for f1 from `from` to 1
+fn curve(
+    from: f64,
+    shape: impl Fn(f64) -> f64 + Send + Sync + 'static,
+) -> impl Fn(usize) -> Vec<Vec<f64>> + Send + Sync + 'static {
+    move |points| {
+        linspace(from, 1., points)
+            .into_iter()
+            .map(|f1| vec![f1, shape(f1)])
+            .collect()
+    }
+}
+
+/// ZDT1 with `variables` variables in \[0, 1\], 30 in the literature.
+/// The front is convex.
+pub fn zdt1(variables: usize) -> Problem<'static, RealVector> {
+    zdt(
+        "ZDT1",
+        unit(variables),
+        average,
+        |f1, g| 1. - (f1 / g).sqrt(),
+        curve(0., |f1| 1. - f1.sqrt()),
+    )
+}
+
+/// ZDT2 with `variables` variables in \[0, 1\], 30 in the literature.
+/// The front is concave.
+pub fn zdt2(variables: usize) -> Problem<'static, RealVector> {
+    zdt(
+        "ZDT2",
+        unit(variables),
+        average,
+        |f1, g| 1. - (f1 / g).powi(2),
+        curve(0., |f1| 1. - f1 * f1),
+    )
+}
+
+/// ZDT3 with `variables` variables in \[0, 1\], 30 in the literature.
+/// The front consists of five disconnected parts.
+pub fn zdt3(variables: usize) -> Problem<'static, RealVector> {
+    let h = |f1: f64, g: f64| 1. - (f1 / g).sqrt() - f1 / g * (10. * PI * f1).sin();
+
+    zdt("ZDT3", unit(variables), average, h, move |points| {
+        // Spread the points over the parts proportionally to their lengths
+        let total: f64 = ZDT3_REGIONS.iter().map(|(a, b)| b - a).sum();
+
+        linspace(0., total, points)
+            .into_iter()
+            .map(|mut t| {
+                let mut f1 = 0.;
+
+                for (a, b) in ZDT3_REGIONS {
+                    f1 = a + t.min(b - a);
+                    if t <= b - a {
+                        break;
+                    }
+                    t -= b - a;
+                }
+
+                vec![f1, h(f1, 1.)]
+            })
+            .collect()
+    })
+}
+
+/// ZDT4 with `variables` variables, 10 in the literature.
+/// The first one is in \[0, 1\], the others in \[-5, 5\].
+/// The front is convex, with 21^9 local fronts.
+pub fn zdt4(variables: usize) -> Problem<'static, RealVector> {
+    let mut bounds = vec![(-5., 5.); variables];
+    if let Some(b) = bounds.first_mut() {
+        *b = (0., 1.);
+    }
+
+    zdt(
+        "ZDT4",
+        bounds,
+        |x| {
+            1. + 10. * x.len() as f64
+                + x.iter()
+                    .map(|x| x * x - 10. * (4. * PI * x).cos())
+                    .sum::<f64>()
+        },
+        |f1, g| 1. - (f1 / g).sqrt(),
+        curve(0., |f1| 1. - f1.sqrt()),
+    )
+}
+
+/// ZDT5 with `variables` binary variables, 11 in the literature:
+/// the first one has 30 bits, every other one 5 bits.
+///
+/// The front is discrete, of 31 points.
+pub fn zdt5(variables: usize) -> Problem<'static, BinaryVector> {
+    assert!(variables >= 2, "ZDT problems need at least two variables");
+
+    let bits = ZDT5_HEAD + ZDT5_TAIL * (variables - 1);
+    let tail = (variables - 1) as f64;
+
+    Problem::new(
+        format!("ZDT5({})", variables),
+        2,
+        |s: &BinaryVector| {
+            let ones = |bits: &[bool]| bits.iter().filter(|b| **b).count();
+
+            let f1 = 1. + ones(&s.bits[..ZDT5_HEAD]) as f64;
+            let g: f64 = s.bits[ZDT5_HEAD..]
+                .chunks(ZDT5_TAIL)
+                .map(|c| match ones(c) {
+                    ZDT5_TAIL => 1.,
+                    u => 2. + u as f64,
+                })
+                .sum();
+
+            vec![f1, g / f1]
+        },
+        move |rng| BinaryVector {
+            bits: (0..bits).map(|_| rng.gen_bool(0.5)).collect(),
+        },
+        move |_| {
+            (1..=ZDT5_HEAD + 1)
+                .map(|f1| vec![f1 as f64, tail / f1 as f64])
+                .collect()
+        },
+    )
+}
+
+/// ZDT6 with `variables` variables in \[0, 1\], 10 in the literature.
+/// The front is concave and the solutions are distributed non-uniformly along it.
+pub fn zdt6(variables: usize) -> Problem<'static, RealVector> {
+    zdt_with(
+        "ZDT6",
+        unit(variables),
+        |x| 1. - (-4. * x[0]).exp() * (6. * PI * x[0]).sin().powi(6),
+        |x| 1. + 9. * (x.iter().sum::<f64>() / x.len() as f64).powf(0.25),
+        |f1, g| 1. - (f1 / g).powi(2),
+        curve(ZDT6_MIN, |f1| 1. - f1 * f1),
+    )
+}
@@ -0,0 +1,139 @@
+use std::time::Duration;
+
+use crate::hypervolume::hypervolume;
+use crate::optimizer::oriented;
+use crate::sorting::constrained_non_dominated_sort;
+use crate::Direction;
+
+/// The reason an optimization process stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Termination {
+    /// A feasible solution was [good enough](crate::Objective::good_enough()) in every objective
+    GoodEnough,
+    /// The [`crate::Evaluator`] decided to stop
+    Evaluator,
+    /// An [`crate::Observer`] asked to stop
+    Observer,
+    /// Saving a checkpoint failed, see `NSGAOptimizer::checkpoint_error()`
+    Checkpoint,
+}
+
+/// A solution found by an optimization along with its objective values
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParetoSolution<S> {
+    pub solution: S,
+    /// Objective values, in the order of [`crate::Meta::objectives()`]
+    pub values: Vec<f64>,
+    /// Front rank within the returned solutions, 0 being the non-dominated front
+    pub front: usize,
+    /// Total constraint violation, 0 for feasible solutions
+    pub violation: f64,
+}
+
+impl<S> ParetoSolution<S> {
+    /// Returns true if the solution satisfies all the constraints
+    pub fn is_feasible(&self) -> bool {
+        self.violation <= 0.
+    }
+}
+
+/// The outcome of an optimization process
+#[derive(Debug, Clone)]
+pub struct OptimizationResult<S> {
+    /// The best solutions found, sorted by front and then by objective values
+    pub solutions: Vec<ParetoSolution<S>>,
+    /// The number of generations produced after the initial population
+    pub generations: usize,
+    /// The total number of evaluated solutions
+    pub evaluations: usize,
+    /// Time spent in the optimization
+    pub elapsed: Duration,
+    /// Why the optimization stopped
+    pub termination: Termination,
+    // Directions of the objectives
+    pub(crate) directions: Vec<Direction>,
+}
+
+impl<S> OptimizationResult<S> {
+    /// Rank the solutions and build a result.
+    /// Objective values are oriented for minimization and get back to natural units.
+    pub(crate) fn new(
+        solutions: Vec<ParetoSolution<S>>,
+        directions: Vec<Direction>,
+        generations: usize,
+        evaluations: usize,
+        elapsed: Duration,
+        termination: Termination,
+    ) -> Self {
+        let fronts = constrained_non_dominated_sort(
+            &solutions.iter().map(|s| &s.values).collect::<Vec<_>>(),
+            &solutions.iter().map(|s| s.violation).collect::<Vec<_>>(),
+        );
+
+        let mut solutions: Vec<_> = solutions
+            .into_iter()
+            .zip(fronts)
+            .map(|(s, front)| ParetoSolution {
+                values: oriented(&directions, &s.values),
+                front,
+                ..s
+            })
+            .collect();
+        solutions.sort_by(|a, b| {
+            a.front.cmp(&b.front).then_with(|| {
+                a.values
+                    .partial_cmp(&b.values)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+        });
+
+        OptimizationResult {
+            solutions,
+            generations,
+            evaluations,
+            elapsed,
+            termination,
+            directions,
+        }
+    }
+
+    /// The hypervolume of the feasible solutions bounded by `reference`,
+    /// see [`crate::hypervolume::hypervolume()`].
+    ///
+    /// The reference point is in natural units, i.e. it's the lower bound
+    /// of maximized objectives.
+    pub fn hypervolume(&self, reference: &[f64]) -> f64 {
+        let values: Vec<_> = self
+            .solutions
+            .iter()
+            .filter(|s| s.is_feasible())
+            .map(|s| oriented(&self.directions, &s.values))
+            .collect();
+
+        hypervolume(&values, &oriented(&self.directions, reference))
+    }
+
+    /// Iterate over the found solutions
+    pub fn iter(&self) -> std::slice::Iter<'_, ParetoSolution<S>> {
+        self.solutions.iter()
+    }
+}
+
+impl<S> IntoIterator for OptimizationResult<S> {
+    type Item = ParetoSolution<S>;
+    type IntoIter = std::vec::IntoIter<ParetoSolution<S>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.solutions.into_iter()
+    }
+}
+
+impl<'r, S> IntoIterator for &'r OptimizationResult<S> {
+    type Item = &'r ParetoSolution<S>;
+    type IntoIter = std::slice::Iter<'r, ParetoSolution<S>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.solutions.iter()
+    }
+}
@@ -0,0 +1,130 @@
+use rand::{Rng, RngCore};
+use std::cmp::Ordering;
+
+use crate::observer::Individual;
+use crate::Solution;
+
+/// A parent selection operator
+///
+/// Closures taking the population along with a random number generator
+/// and returning an index are selection operators too:
+/// ```ignore
+/// let optimizer = NSGAOptimizer::new(meta)
+///     .selection(|pop: &[Individual<_>], rng: &mut dyn RngCore| rng.gen_range(0..pop.len()));
+/// ```
+pub trait Selection<S: Solution> {
+    /// Pick a parent out of the current `population`, sorted by front.
+    /// Returns its index.
+    fn select(&mut self, population: &[Individual<S>], rng: &mut dyn RngCore) -> usize;
+}
+
+impl<S, F> Selection<S> for F
+where
+    S: Solution,
+    F: FnMut(&[Individual<S>], &mut dyn RngCore) -> usize,
+{
+    fn select(&mut self, population: &[Individual<S>], rng: &mut dyn RngCore) -> usize {
+        self(population, rng)
+    }
+}
+
+/// Tournament on the total constraint violation first,
+/// then on the front rank and then on the crowding distance.
+///
+/// `size` random members take part in every tournament, a binary one is used by default.
+/// Larger tournaments increase the selection pressure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConstrainedTournament {
+    pub size: usize,
+}
+
+impl Default for ConstrainedTournament {
+    fn default() -> Self {
+        ConstrainedTournament { size: 2 }
+    }
+}
+
+impl<S: Solution> Selection<S> for ConstrainedTournament {
+    fn select(&mut self, population: &[Individual<S>], rng: &mut dyn RngCore) -> usize {
+        tournament(population, self.size, rng, |a, b| {
+            a.violation
+                .partial_cmp(&b.violation)
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| crowded(a, b))
+        })
+    }
+}
+
+/// The classic NSGA-II crowded tournament: the front rank wins first,
+/// then the crowding distance.
+///
+/// `size` random members take part in every tournament.
+/// Front ranks already account for constraints, but unlike [`ConstrainedTournament`]
+/// the violations are not compared directly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CrowdedTournament {
+    pub size: usize,
+}
+
+impl Default for CrowdedTournament {
+    fn default() -> Self {
+        CrowdedTournament { size: 2 }
+    }
+}
+
+impl<S: Solution> Selection<S> for CrowdedTournament {
+    fn select(&mut self, population: &[Individual<S>], rng: &mut dyn RngCore) -> usize {
+        tournament(population, self.size, rng, crowded)
+    }
+}
+
+/// Pick a random parent, without any selection pressure
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RandomSelection;
+
+impl<S: Solution> Selection<S> for RandomSelection {
+    fn select(&mut self, population: &[Individual<S>], rng: &mut dyn RngCore) -> usize {
+        rng.gen_range(0..population.len())
+    }
+}
+
+fn crowded<S: Solution>(a: &Individual<S>, b: &Individual<S>) -> Ordering {
+    a.front.cmp(&b.front).then_with(|| {
+        b.distance
+            .partial_cmp(&a.distance)
+            .unwrap_or(Ordering::Equal)
+    })
+}
+
+// Pick the best out of `size` random members, `Ordering::Less` being better.
+// Ties are broken randomly.
+fn tournament<S: Solution>(
+    population: &[Individual<S>],
+    size: usize,
+    rng: &mut dyn RngCore,
+    cmp: impl Fn(&Individual<S>, &Individual<S>) -> Ordering,
+) -> usize {
+    let mut best = rng.gen_range(0..population.len());
+    let mut ties = 1;
+
+    for _ in 1..size {
+        let i = rng.gen_range(0..population.len());
+
+        match cmp(&population[i], &population[best]) {
+            Ordering::Less => {
+                best = i;
+                ties = 1;
+            }
+            Ordering::Equal => {
+                // Keep every tied member with the same probability
+                ties += 1;
+                if rng.gen_range(0..ties) == 0 {
+                    best = i;
+                }
+            }
+            Ordering::Greater => {}
+        }
+    }
+
+    best
+}
@@ -0,0 +1,174 @@
+use std::cmp::Ordering;
+
+/// Returns true if `vals1` Pareto-dominates `vals2`
+pub(crate) fn dominates(vals1: &[f64], vals2: &[f64]) -> bool {
+    let mut better = false;
+
+    for (v1, v2) in vals1.iter().zip(vals2) {
+        if v1 > v2 {
+            return false;
+        }
+
+        if v1 < v2 {
+            better = true;
+        }
+    }
+
+    better
+}
+
+/// Returns true if `vals1` constraint-dominates `vals2`: a feasible vector always wins
+/// over an infeasible one and the smaller total violation wins among infeasible vectors
+pub(crate) fn constrained_dominates(
+    vals1: &[f64],
+    violation1: f64,
+    vals2: &[f64],
+    violation2: f64,
+) -> bool {
+    if violation1 <= 0. && violation2 <= 0. {
+        dominates(vals1, vals2)
+    } else {
+        violation1 < violation2
+    }
+}
+
+/// Compute crowding distances for the members of a single front.
+///
+/// For every objective the front is sorted by its value, the boundary members
+/// get an infinite distance and every other member accumulates the normalized
+/// distance between its two neighbours.
+pub(crate) fn crowding_distance<V: AsRef<[f64]>>(front: &[V]) -> Vec<f64> {
+    let mut distances = vec![0.; front.len()];
+    let objectives = front.first().map_or(0, |v| v.as_ref().len());
+    let mut order: Vec<usize> = (0..front.len()).collect();
+
+    for obj in 0..objectives {
+        let val = |i: usize| front[i].as_ref()[obj];
+
+        order.sort_by(|a, b| val(*a).partial_cmp(&val(*b)).unwrap_or(Ordering::Equal));
+
+        let (first, last) = (order[0], order[order.len() - 1]);
+        distances[first] = f64::INFINITY;
+        distances[last] = f64::INFINITY;
+
+        let range = val(last) - val(first);
+        if range <= 0. {
+            continue;
+        }
+
+        for w in order.windows(3) {
+            distances[w[1]] += (val(w[2]) - val(w[0])) / range;
+        }
+    }
+
+    distances
+}
+
+/// Assign a front index to every objective vector using constraint-domination.
+///
+/// Feasible vectors are sorted with [`non_dominated_sort()`], infeasible ones
+/// follow them, one front per distinct violation amount.
+pub(crate) fn constrained_non_dominated_sort<V: AsRef<[f64]>>(
+    values: &[V],
+    violations: &[f64],
+) -> Vec<usize> {
+    let (feasible, mut infeasible): (Vec<usize>, Vec<usize>) =
+        (0..values.len()).partition(|&i| violations[i] <= 0.);
+
+    let mut fronts = vec![0; values.len()];
+    let ranks = non_dominated_sort(&feasible.iter().map(|&i| &values[i]).collect::<Vec<_>>());
+    let mut front = ranks.iter().max().map_or(0, |m| m + 1);
+
+    for (i, rank) in feasible.into_iter().zip(ranks) {
+        fronts[i] = rank;
+    }
+
+    infeasible.sort_by(|a, b| {
+        violations[*a]
+            .partial_cmp(&violations[*b])
+            .unwrap_or(Ordering::Equal)
+    });
+
+    for (k, i) in infeasible.iter().enumerate() {
+        if k > 0 && violations[*i] > violations[infeasible[k - 1]] {
+            front += 1;
+        }
+
+        fronts[*i] = front;
+    }
+
+    fronts
+}
+
+/// Assign a front index to every objective vector, starting from 0.
+///
+/// Vectors are visited in lexicographic order, so a vector can only be dominated
+/// by the ones visited before it. Bi-objective problems use a binary search
+/// over the fronts, everything else uses
+/// [Efficient Non-dominated Sort](https://doi.org/10.1109/TEVC.2014.2308305)
+/// with a sequential search.
+pub(crate) fn non_dominated_sort<V: AsRef<[f64]>>(values: &[V]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+
+    order.sort_by(|a, b| {
+        values[*a]
+            .as_ref()
+            .iter()
+            .zip(values[*b].as_ref())
+            .map(|(v1, v2)| v1.partial_cmp(v2).unwrap_or(Ordering::Equal))
+            .find(|o| *o != Ordering::Equal)
+            .unwrap_or(Ordering::Equal)
+    });
+
+    match values.first().map(|v| v.as_ref().len()) {
+        Some(2) => sort_2d(values, &order),
+        _ => sort_ens(values, &order),
+    }
+}
+
+// With two objectives the last member of a front has the smallest second objective,
+// so it dominates a new vector if and only if the whole front does.
+fn sort_2d<V: AsRef<[f64]>>(values: &[V], order: &[usize]) -> Vec<usize> {
+    let mut fronts = vec![0; values.len()];
+    let mut last: Vec<usize> = Vec::new();
+
+    for &i in order {
+        let v = values[i].as_ref();
+        let front = last.partition_point(|&j| dominates(values[j].as_ref(), v));
+
+        if front == last.len() {
+            last.push(i);
+        } else {
+            last[front] = i;
+        }
+
+        fronts[i] = front;
+    }
+
+    fronts
+}
+
+fn sort_ens<V: AsRef<[f64]>>(values: &[V], order: &[usize]) -> Vec<usize> {
+    let mut fronts = vec![0; values.len()];
+    let mut members: Vec<Vec<usize>> = Vec::new();
+
+    for &i in order {
+        let v = values[i].as_ref();
+
+        // Recently added members are the most likely to dominate
+        let front = members
+            .iter()
+            .position(|m| !m.iter().rev().any(|&j| dominates(values[j].as_ref(), v)))
+            .unwrap_or(members.len());
+
+        if front == members.len() {
+            members.push(vec![i]);
+        } else {
+            members[front].push(i);
+        }
+
+        fronts[i] = front;
+    }
+
+    fronts
+}
@@ -0,0 +1,348 @@
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use std::cmp::Ordering;
+use std::time::Instant;
+
+use crate::archive::ParetoArchive;
+use crate::optimizer::{directions, evaluate, good_enough, oriented};
+use crate::result::{OptimizationResult, Termination};
+use crate::sorting::constrained_dominates;
+use crate::{Evaluator, Meta, Progress, Solution};
+
+// A population member along with its SPEA2 fitness
+struct Member<S> {
+    sol: S,
+    values: Vec<f64>,
+    violation: f64,
+    fitness: f64,
+}
+
+/// [SPEA2](https://doi.org/10.3929/ethz-a-004284029) optimizer:
+/// the Strength Pareto Evolutionary Algorithm 2.
+///
+/// Every solution gets a fitness made of its raw fitness, the total strength
+/// of the solutions dominating it, and a density estimate based on the distance
+/// to its k-th nearest neighbour. Non-dominated solutions are kept in an internal
+/// archive of fixed size, truncated by removing the solutions closest to each other.
+/// Parents are picked from that archive using binary tournaments.
+///
+/// It works with the same [`Meta`] params as [`crate::NSGAOptimizer`],
+/// domination accounts for constraints the same way.
+pub struct SPEA2Optimizer<'a, S: Solution> {
+    meta: Box<dyn Meta<'a, S> + 'a>,
+    rng: ChaCha8Rng,
+    elite_size: Option<usize>,
+    archive: ParetoArchive<S>,
+    elite: Vec<Member<S>>,
+    generation: usize,
+    evaluations: usize,
+}
+
+impl<'a, S> SPEA2Optimizer<'a, S>
+where
+    S: Solution,
+{
+    /// Instantiate a new optimizer with a given meta params
+    pub fn new(meta: impl Meta<'a, S> + 'a) -> Self {
+        let archive = ParetoArchive::new().capacity(meta.population_size());
+
+        SPEA2Optimizer {
+            meta: Box::new(meta),
+            rng: ChaCha8Rng::from_entropy(),
+            elite_size: None,
+            archive,
+            elite: Vec::new(),
+            generation: 0,
+            evaluations: 0,
+        }
+    }
+
+    /// Seed the random number generator, see [`crate::NSGAOptimizer::seed()`]
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.rng = ChaCha8Rng::seed_from_u64(seed);
+        self
+    }
+
+    /// Set the size of the internal archive parents are picked from,
+    /// [`Meta::population_size()`] by default
+    pub fn elite_size(mut self, size: usize) -> Self {
+        self.elite_size = Some(size.max(1));
+        self
+    }
+
+    /// Set an archive to keep the best solutions found during the optimization,
+    /// see [`crate::NSGAOptimizer::archive()`]
+    pub fn archive(mut self, archive: ParetoArchive<S>) -> Self {
+        self.archive = archive;
+        self
+    }
+
+    /// Run an optimization process using `eval` to determine termination condition,
+    /// see [`crate::NSGAOptimizer::optimize()`]
+    pub fn optimize(&mut self, mut eval: Box<dyn Evaluator>) -> OptimizationResult<S> {
+        let started = Instant::now();
+
+        if self.elite.is_empty() {
+            let pop = (0..self.meta.population_size())
+                .map(|_| self.meta.random_solution(&mut self.rng))
+                .collect();
+
+            self.advance(pop);
+        }
+
+        let termination = loop {
+            // Check if there's a good-enough solution already
+            let objectives = self.meta.objectives();
+            if self
+                .elite
+                .iter()
+                .any(|m| good_enough(objectives, &m.values, m.violation))
+            {
+                break Termination::GoodEnough;
+            }
+
+            // Check if we can already terminate
+            let progress = Progress::new(
+                directions(objectives),
+                self.generation,
+                self.evaluations,
+                started.elapsed(),
+                self.elite
+                    .iter()
+                    .map(|m| (m.values.as_slice(), m.violation)),
+            );
+
+            if eval.can_terminate(&progress) {
+                break Termination::Evaluator;
+            }
+
+            let pop = self.offspring();
+            self.advance(pop);
+            self.generation += 1;
+        };
+
+        OptimizationResult::new(
+            self.archive.iter().cloned().collect(),
+            directions(self.meta.objectives()),
+            self.generation,
+            self.evaluations,
+            started.elapsed(),
+            termination,
+        )
+    }
+
+    /// Return the best solutions found so far
+    pub fn solutions(&self) -> impl Iterator<Item = &S> {
+        self.archive.iter().map(|s| &s.solution)
+    }
+
+    // Produce the next population out of the elite using binary tournaments
+    fn offspring(&mut self) -> Vec<S> {
+        let pop_size = self.meta.population_size();
+        let crossover_odds = self.meta.crossover_odds();
+        let mutation_odds = self.meta.mutation_odds();
+
+        let mut child_pop: Vec<S> = Vec::with_capacity(pop_size + 1);
+
+        while child_pop.len() < pop_size {
+            let p1 = self.tournament();
+            let p2 = self.tournament();
+
+            let mut c1 = self.elite[p1].sol.clone();
+            let mut c2 = self.elite[p2].sol.clone();
+
+            if self.rng.gen_ratio(crossover_odds.0, crossover_odds.1) {
+                c1.crossover(&mut c2, &mut self.rng);
+            };
+
+            if self.rng.gen_ratio(mutation_odds.0, mutation_odds.1) {
+                c1.mutate(&mut self.rng);
+            };
+
+            if self.rng.gen_ratio(mutation_odds.0, mutation_odds.1) {
+                c2.mutate(&mut self.rng);
+            };
+
+            child_pop.push(c1);
+            child_pop.push(c2);
+        }
+
+        child_pop.truncate(pop_size);
+        child_pop
+    }
+
+    // Binary tournament on the fitness, lower is better
+    fn tournament(&mut self) -> usize {
+        let a = self.rng.gen_range(0..self.elite.len());
+        let b = self.rng.gen_range(0..self.elite.len());
+
+        if self.elite[b].fitness < self.elite[a].fitness {
+            b
+        } else {
+            a
+        }
+    }
+
+    // Evaluate a new population and select the next elite
+    // out of it together with the current elite
+    fn advance(&mut self, pop: Vec<S>) {
+        let objectives = self.meta.objectives();
+        let values = self.meta.values();
+        let constraints = self.meta.constraints();
+        let directions = directions(objectives);
+
+        self.evaluations += pop.len();
+        let mut union: Vec<Member<S>> = pop
+            .into_iter()
+            .map(|sol| {
+                let (values, violation) = evaluate(objectives, values, constraints, &sol);
+
+                Member {
+                    sol,
+                    values: oriented(&directions, &values),
+                    violation,
+                    fitness: 0.,
+                }
+            })
+            .collect();
+
+        union.append(&mut self.elite);
+
+        assign_fitness(&mut union);
+
+        let size = self.elite_size.unwrap_or(self.meta.population_size());
+        self.elite = environmental_selection(union, size);
+
+        for m in self.elite.iter().filter(|m| m.fitness < 1.) {
+            self.archive
+                .insert_with_violation(m.sol.clone(), m.values.clone(), m.violation);
+        }
+    }
+}
+
+// Assign SPEA2 fitness: the raw fitness plus the density,
+// the latter is always less than 1 so non-dominated members are the ones below 1
+fn assign_fitness<S>(members: &mut [Member<S>]) {
+    let n = members.len();
+
+    let dominates = |i: usize, j: usize| {
+        constrained_dominates(
+            &members[i].values,
+            members[i].violation,
+            &members[j].values,
+            members[j].violation,
+        )
+    };
+
+    // How many members every member dominates
+    let strength: Vec<usize> = (0..n)
+        .map(|i| (0..n).filter(|j| dominates(i, *j)).count())
+        .collect();
+
+    // The total strength of the members dominating every member
+    let raw: Vec<usize> = (0..n)
+        .map(|j| {
+            (0..n)
+                .filter(|i| dominates(*i, j))
+                .map(|i| strength[i])
+                .sum()
+        })
+        .collect();
+
+    let k = (n as f64).sqrt() as usize;
+    let density: Vec<f64> = (0..n)
+        .map(|i| {
+            let mut distances: Vec<f64> = (0..n)
+                .filter(|j| *j != i)
+                .map(|j| distance(&members[i].values, &members[j].values))
+                .collect();
+
+            distances.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+
+            let sigma = distances
+                .get(k.min(distances.len()).saturating_sub(1))
+                .copied()
+                .unwrap_or(0.);
+
+            1. / (sigma + 2.)
+        })
+        .collect();
+
+    for (i, m) in members.iter_mut().enumerate() {
+        m.fitness = raw[i] as f64 + density[i];
+    }
+}
+
+// Keep every non-dominated member, filling up with the fittest dominated ones
+// or truncating the most crowded ones, so that exactly `size` members remain
+fn environmental_selection<S>(mut members: Vec<Member<S>>, size: usize) -> Vec<Member<S>> {
+    members.sort_by(|a, b| a.fitness.partial_cmp(&b.fitness).unwrap_or(Ordering::Equal));
+
+    let non_dominated = members.iter().take_while(|m| m.fitness < 1.).count();
+
+    if non_dominated <= size {
+        members.truncate(size);
+        return members;
+    }
+
+    members.truncate(non_dominated);
+    truncate(&mut members, size);
+
+    members
+}
+
+// Repeatedly remove the member closest to the others: the one with the smallest
+// distance to its nearest neighbour, ties broken by the second nearest and so on
+fn truncate<S>(members: &mut Vec<Member<S>>, size: usize) {
+    let n = members.len();
+    let matrix: Vec<Vec<f64>> = (0..n)
+        .map(|i| {
+            (0..n)
+                .map(|j| distance(&members[i].values, &members[j].values))
+                .collect()
+        })
+        .collect();
+
+    let mut alive: Vec<usize> = (0..n).collect();
+
+    while alive.len() > size {
+        let neighbours: Vec<Vec<f64>> = alive
+            .iter()
+            .map(|i| {
+                let mut d: Vec<f64> = alive
+                    .iter()
+                    .filter(|j| *j != i)
+                    .map(|j| matrix[*i][*j])
+                    .collect();
+
+                d.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+                d
+            })
+            .collect();
+
+        let closest = (0..alive.len())
+            .min_by(|a, b| {
+                neighbours[*a]
+                    .partial_cmp(&neighbours[*b])
+                    .unwrap_or(Ordering::Equal)
+            })
+            .unwrap();
+
+        alive.remove(closest);
+    }
+
+    let mut index = 0;
+    members.retain(|_| {
+        let keep = alive.binary_search(&index).is_ok();
+        index += 1;
+        keep
+    });
+}
+
+fn distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter()
+        .zip(b)
+        .map(|(x, y)| (x - y).powi(2))
+        .sum::<f64>()
+        .sqrt()
+}
@@ -0,0 +1,270 @@
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore};
+use std::cmp::Ordering;
+
+/// A strategy to pick survivors from the last front
+/// that doesn't fit into the next population entirely
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Survival {
+    /// NSGA-II: prefer the candidates with the largest crowding distance
+    #[default]
+    CrowdingDistance,
+
+    /// [NSGA-III](https://doi.org/10.1109/TEVC.2013.2281535):
+    /// niche-preserving selection around structured reference directions.
+    ///
+    /// Reference directions are placed on a unit simplex using
+    /// Das and Dennis's approach with the given number of `divisions`
+    /// along every objective, producing `C(M + divisions - 1, divisions)`
+    /// directions for `M` objectives. The population size should be
+    /// close to the number of directions.
+    ///
+    /// Crowding distance stops separating solutions well beyond three objectives,
+    /// so this is a better choice for many-objective problems.
+    ReferencePoints { divisions: usize },
+}
+
+/// Generate structured reference directions on a unit simplex
+/// using Das and Dennis's approach
+pub(crate) fn reference_directions(objectives: usize, divisions: usize) -> Vec<Vec<f64>> {
+    fn fill(
+        dirs: &mut Vec<Vec<f64>>,
+        current: &mut Vec<f64>,
+        objectives: usize,
+        left: usize,
+        divisions: usize,
+    ) {
+        if current.len() == objectives - 1 {
+            current.push(left as f64 / divisions as f64);
+            dirs.push(current.clone());
+            current.pop();
+        } else {
+            for i in 0..=left {
+                current.push(i as f64 / divisions as f64);
+                fill(dirs, current, objectives, left - i, divisions);
+                current.pop();
+            }
+        }
+    }
+
+    let mut dirs = Vec::new();
+
+    if objectives > 0 && divisions > 0 {
+        fill(&mut dirs, &mut Vec::new(), objectives, divisions, divisions);
+    }
+
+    dirs
+}
+
+/// Pick `k` members of the `last` front to join the `selected` ones
+/// using NSGA-III niching around reference `directions`.
+///
+/// Returns the indices into `last`.
+pub(crate) fn select_by_reference<V: AsRef<[f64]>>(
+    selected: &[V],
+    last: &[V],
+    k: usize,
+    directions: &[Vec<f64>],
+    rng: &mut dyn RngCore,
+) -> Vec<usize> {
+    let all: Vec<&[f64]> = selected
+        .iter()
+        .chain(last.iter())
+        .map(|v| v.as_ref())
+        .collect();
+
+    let normalized = normalize(&all);
+
+    // Associate everyone with the closest reference direction
+    let (niches, distances): (Vec<usize>, Vec<f64>) =
+        normalized.iter().map(|v| associate(v, directions)).unzip();
+
+    let mut niche_count = vec![0_usize; directions.len()];
+    for niche in &niches[..selected.len()] {
+        niche_count[*niche] += 1;
+    }
+
+    let mut candidates: Vec<Vec<usize>> = vec![Vec::new(); directions.len()];
+    for i in 0..last.len() {
+        candidates[niches[selected.len() + i]].push(i);
+    }
+
+    let mut available: Vec<usize> = (0..directions.len())
+        .filter(|j| !candidates[*j].is_empty())
+        .collect();
+
+    let mut picked = Vec::with_capacity(k);
+
+    while picked.len() < k && !available.is_empty() {
+        let min = available.iter().map(|j| niche_count[*j]).min().unwrap();
+        let least_crowded: Vec<usize> = available
+            .iter()
+            .copied()
+            .filter(|j| niche_count[*j] == min)
+            .collect();
+
+        let j = *least_crowded.choose(rng).unwrap();
+        let members = &mut candidates[j];
+
+        // An empty niche takes the member closest to its direction,
+        // otherwise any member will do
+        let pos = if niche_count[j] == 0 {
+            (0..members.len())
+                .min_by(|a, b| {
+                    let da = distances[selected.len() + members[*a]];
+                    let db = distances[selected.len() + members[*b]];
+
+                    da.partial_cmp(&db).unwrap_or(Ordering::Equal)
+                })
+                .unwrap()
+        } else {
+            rng.gen_range(0..members.len())
+        };
+
+        picked.push(members.swap_remove(pos));
+        niche_count[j] += 1;
+
+        if members.is_empty() {
+            available.retain(|a| *a != j);
+        }
+    }
+
+    picked
+}
+
+// Translate objective vectors by the ideal point and scale them
+// by the intercepts of the hyperplane through the extreme points.
+// Non-finite values are left out of both, so they can't spoil the others
+fn normalize(values: &[&[f64]]) -> Vec<Vec<f64>> {
+    let m = values.first().map_or(0, |v| v.len());
+
+    let ideal: Vec<f64> = (0..m)
+        .map(|i| {
+            let z = values
+                .iter()
+                .map(|v| v[i])
+                .filter(|x| x.is_finite())
+                .fold(f64::INFINITY, f64::min);
+
+            if z.is_finite() {
+                z
+            } else {
+                0.
+            }
+        })
+        .collect();
+
+    let translated: Vec<Vec<f64>> = values
+        .iter()
+        .map(|v| v.iter().zip(&ideal).map(|(x, z)| x - z).collect())
+        .collect();
+
+    // Extreme points minimize the achievement scalarizing function along every axis
+    let extremes: Vec<&Vec<f64>> = (0..m)
+        .map(|axis| {
+            translated
+                .iter()
+                .min_by(|a, b| {
+                    asf(a, axis)
+                        .partial_cmp(&asf(b, axis))
+                        .unwrap_or(Ordering::Equal)
+                })
+                .unwrap()
+        })
+        .collect();
+
+    let nadir: Vec<f64> = (0..m)
+        .map(|i| {
+            translated
+                .iter()
+                .map(|v| v[i])
+                .filter(|x| x.is_finite())
+                .fold(0., f64::max)
+        })
+        .collect();
+
+    let intercepts = match solve(&extremes) {
+        Some(b) if b.iter().all(|x| x.is_finite() && *x > 1e-10) => {
+            b.iter().map(|x| 1. / x).collect()
+        }
+        // Degenerate hyperplane, fall back to the worst values
+        _ => nadir,
+    };
+
+    translated
+        .into_iter()
+        .map(|v| {
+            v.iter()
+                .zip(&intercepts)
+                .map(|(x, a)| if *a > 1e-10 { x / a } else { *x })
+                .collect()
+        })
+        .collect()
+}
+
+fn asf(v: &[f64], axis: usize) -> f64 {
+    v.iter()
+        .enumerate()
+        .map(|(i, x)| if i == axis { *x } else { x / 1e-6 })
+        .fold(f64::NEG_INFINITY, f64::max)
+}
+
+// Solve `points * b = 1` using Gaussian elimination with partial pivoting
+fn solve(points: &[&Vec<f64>]) -> Option<Vec<f64>> {
+    let m = points.len();
+    let mut a: Vec<Vec<f64>> = points
+        .iter()
+        .map(|p| {
+            let mut row = (*p).clone();
+            row.push(1.);
+            row
+        })
+        .collect();
+
+    for col in 0..m {
+        let pivot = (col..m).max_by(|x, y| {
+            a[*x][col]
+                .abs()
+                .partial_cmp(&a[*y][col].abs())
+                .unwrap_or(Ordering::Equal)
+        })?;
+
+        if a[pivot][col].abs() < 1e-12 {
+            return None;
+        }
+
+        a.swap(col, pivot);
+        let pivot_row = a[col].clone();
+
+        for (r, row) in a.iter_mut().enumerate() {
+            if r != col {
+                let factor = row[col] / pivot_row[col];
+
+                for (v, p) in row.iter_mut().zip(&pivot_row).skip(col) {
+                    *v -= factor * p;
+                }
+            }
+        }
+    }
+
+    Some((0..m).map(|i| a[i][m] / a[i][i]).collect())
+}
+
+// Return the closest reference direction along with the perpendicular distance to it
+fn associate(v: &[f64], directions: &[Vec<f64>]) -> (usize, f64) {
+    directions
+        .iter()
+        .map(|w| {
+            let norm: f64 = w.iter().map(|x| x * x).sum();
+            let proj: f64 = w.iter().zip(v).map(|(a, b)| a * b).sum::<f64>() / norm;
+
+            w.iter()
+                .zip(v)
+                .map(|(a, b)| (b - proj * a).powi(2))
+                .sum::<f64>()
+                .sqrt()
+        })
+        .enumerate()
+        .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal))
+        .unwrap()
+}
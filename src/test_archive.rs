@@ -0,0 +1,77 @@
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    fn values(archive: &ParetoArchive<usize>) -> Vec<Vec<f64>> {
+        let mut values: Vec<_> = archive.iter().map(|s| s.values.clone()).collect();
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        values
+    }
+
+    #[test]
+    fn archive_non_dominated() {
+        let mut archive = ParetoArchive::new();
+
+        assert!(archive.insert(0, vec![1., 5.]));
+        assert!(archive.insert(1, vec![5., 1.]));
+        assert!(archive.insert(2, vec![3., 3.]));
+        // Dominated
+        assert!(!archive.insert(3, vec![4., 4.]));
+        // Duplicate
+        assert!(!archive.insert(4, vec![3., 3.]));
+        // Ties in one objective, better in the other one
+        assert!(archive.insert(5, vec![1., 4.]));
+
+        assert_eq!(
+            values(&archive),
+            vec![vec![1., 4.], vec![3., 3.], vec![5., 1.]]
+        );
+    }
+
+    #[test]
+    fn archive_constraints() {
+        let mut archive = ParetoArchive::new();
+
+        assert!(archive.insert_with_violation(0, vec![0., 0.], 2.));
+        assert!(archive.insert_with_violation(1, vec![5., 5.], 1.));
+        assert!(!archive.insert_with_violation(2, vec![0., 0.], 3.));
+        assert_eq!(values(&archive), vec![vec![5., 5.]]);
+
+        // Any feasible solution wins
+        assert!(archive.insert(3, vec![9., 9.]));
+        assert!(!archive.insert_with_violation(4, vec![0., 0.], 0.5));
+        assert_eq!(values(&archive), vec![vec![9., 9.]]);
+    }
+
+    #[test]
+    fn archive_epsilon() {
+        let mut archive = ParetoArchive::new().epsilon(vec![1.]);
+
+        assert!(archive.insert(0, vec![0.5, 3.5]));
+        // Same box, closer to its corner
+        assert!(archive.insert(1, vec![0.2, 3.1]));
+        // Same box, further from its corner
+        assert!(!archive.insert(2, vec![0.9, 3.05]));
+        // The box is dominated by the (0, 3) one
+        assert!(!archive.insert(3, vec![1.1, 3.9]));
+        assert!(archive.insert(4, vec![2.5, 0.5]));
+
+        assert_eq!(values(&archive), vec![vec![0.2, 3.1], vec![2.5, 0.5]]);
+    }
+
+    #[test]
+    fn archive_capacity() {
+        let mut archive = ParetoArchive::new().capacity(3);
+
+        for i in 0..=10 {
+            archive.insert(i, vec![i as f64, 10. - i as f64]);
+        }
+
+        assert_eq!(archive.len(), 3);
+
+        // Boundary solutions are kept
+        let vals = values(&archive);
+        assert_eq!(vals[0], vec![0., 10.]);
+        assert_eq!(vals[2], vec![10., 0.]);
+    }
+}
@@ -0,0 +1,39 @@
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    // Values of `n` mutations of a candidate at `val` within [0, 1]
+    fn mutations(val: f64, n: usize) -> Vec<f64> {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+
+        (0..n)
+            .map(|_| {
+                let mut c = CandidateF64 {
+                    val,
+                    range_start: 0.,
+                    range_end: 1.,
+                };
+                c.mutate(&mut rng);
+                c.val
+            })
+            .collect()
+    }
+
+    #[test]
+    fn mutation_directions() {
+        // Mutations move values up as often as down
+        let up = mutations(0.5, 1000).iter().filter(|v| **v > 0.5).count();
+        assert!((400..600).contains(&up), "{}", up);
+
+        // Even from the ends of the range, which are never crossed
+        let start = mutations(0., 100);
+        assert!(start.iter().all(|v| (0.0..=1.).contains(v)));
+        assert!(start.iter().any(|v| *v > 0.));
+
+        let end = mutations(1., 100);
+        assert!(end.iter().all(|v| (0.0..=1.).contains(v)));
+        assert!(end.iter().any(|v| *v < 1.));
+    }
+}
@@ -0,0 +1,101 @@
+#[cfg(test)]
+mod test {
+    use crate::problems::zdt1;
+    use crate::test::*;
+    use crate::*;
+    use std::time::Duration;
+
+    #[test]
+    fn evaluator_limits() {
+        let run = |eval: Box<dyn Evaluator>| {
+            NSGAOptimizer::new(zdt1(10).population_size(POPULATION_SIZE))
+                .seed(0)
+                .optimize(eval)
+        };
+
+        let res = run(Box::new(Any::new(vec![
+            Box::new(TimeBudget::new(Duration::from_secs(3600))),
+            Box::new(MaxGenerations::new(5)),
+        ])));
+        assert_eq!(res.generations, 5);
+
+        // The initial population and four offspring ones
+        let res = run(Box::new(MaxEvaluations::new(5 * POPULATION_SIZE)));
+        assert_eq!(res.generations, 4);
+        assert_eq!(res.evaluations, 5 * POPULATION_SIZE);
+
+        let res = run(Box::new(TimeBudget::new(Duration::ZERO)));
+        assert_eq!(res.generations, 0);
+    }
+
+    fn progress(generation: usize, front: Vec<Vec<f64>>) -> Progress {
+        Progress {
+            generation,
+            violations: vec![0.; front.len()],
+            population: front.clone(),
+            front,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn evaluator_all() {
+        let mut all = All::new(vec![
+            Box::new(MaxGenerations::new(3)),
+            Box::new(MaxGenerations::new(5)),
+        ]);
+
+        assert!(!all.can_terminate(&progress(4, vec![])));
+        assert!(all.can_terminate(&progress(5, vec![])));
+        assert!(!All::new(vec![]).can_terminate(&progress(5, vec![])));
+    }
+
+    #[test]
+    fn evaluator_target_values() {
+        let mut target = TargetValues::new(vec![1., 2.]);
+        let progress = |front| Progress {
+            directions: vec![Direction::Minimize, Direction::Maximize],
+            ..progress(0, front)
+        };
+
+        assert!(!target.can_terminate(&progress(vec![vec![1., 1.], vec![1.5, 3.]])));
+        assert!(target.can_terminate(&progress(vec![vec![1.5, 3.], vec![1., 3.]])));
+    }
+
+    #[test]
+    fn evaluator_hypervolume_stagnation() {
+        let mut eval = HypervolumeStagnation::new(vec![4., 4.], 2);
+
+        assert!(!eval.can_terminate(&progress(0, vec![vec![3., 3.]])));
+        assert!(!eval.can_terminate(&progress(1, vec![vec![2., 2.]])));
+        assert!(!eval.can_terminate(&progress(2, vec![vec![2., 2.]])));
+        assert!(eval.can_terminate(&progress(3, vec![vec![2., 2.]])));
+    }
+
+    #[test]
+    fn default_evaluator() {
+        let mut eval = DefaultEvaluator::new(2);
+
+        assert!(!eval.can_terminate(&progress(0, vec![vec![2., 3.], vec![3., 2.]])));
+
+        // The same front in a different order, then a point dominating one of it
+        assert!(!eval.can_terminate(&progress(1, vec![vec![3., 2.], vec![2., 3.]])));
+        assert!(!eval.can_terminate(&progress(2, vec![vec![1., 3.]])));
+
+        // Neither filling the gaps nor dominated points count
+        assert!(!eval.can_terminate(&progress(3, vec![vec![2.5, 2.5]])));
+        assert!(eval.can_terminate(&progress(4, vec![vec![3., 3.]])));
+    }
+
+    #[test]
+    fn default_evaluator_capacity() {
+        let mut eval = DefaultEvaluator::new(2);
+
+        assert!(!eval.can_terminate(&progress(0, vec![vec![0., 10.], vec![10., 0.]])));
+        assert!(!eval.can_terminate(&progress(1, vec![vec![5., 5.], vec![4., 6.]])));
+
+        // As many solutions as in the population are kept, the boundary ones,
+        // so dominating a dropped one isn't an improvement
+        assert!(eval.can_terminate(&progress(2, vec![vec![4.5, 4.5]])));
+    }
+}
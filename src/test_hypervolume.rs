@@ -0,0 +1,83 @@
+#[cfg(test)]
+mod test {
+    use crate::hypervolume::{estimate, hypervolume};
+    use crate::test::*;
+    use crate::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn hypervolume_2d() {
+        let front = vec![vec![1., 3.], vec![2., 2.], vec![3., 1.]];
+        assert_eq!(hypervolume(&front, &[4., 4.]), 6.);
+
+        // Dominated points, duplicates and points beyond the reference don't count
+        let noisy = vec![
+            vec![1., 3.],
+            vec![2., 2.],
+            vec![2., 2.],
+            vec![3., 3.],
+            vec![3., 1.],
+            vec![5., 0.],
+        ];
+        assert_eq!(hypervolume(&noisy, &[4., 4.]), 6.);
+
+        assert_eq!(hypervolume::<Vec<f64>>(&[], &[4., 4.]), 0.);
+    }
+
+    #[test]
+    fn hypervolume_3d() {
+        assert_eq!(hypervolume(&[[0., 0., 0.]], &[1., 1., 1.]), 1.);
+
+        // Two boxes of 0.5 and 0.25 overlapping by 0.125
+        let front = vec![vec![0., 0., 0.5], vec![0.5, 0.5, 0.]];
+        assert_eq!(hypervolume(&front, &[1., 1., 1.]), 0.625);
+
+        // Grid points on the x + y + z = 2 plane
+        let mut staircase = Vec::new();
+        for x in 0..3 {
+            for y in 0..3 - x {
+                staircase.push(vec![x as f64, y as f64, (2 - x - y) as f64]);
+            }
+        }
+        // Unit cubes at i + j + k >= 2 are dominated: all 27 but 4 of them
+        assert_eq!(hypervolume(&staircase, &[3., 3., 3.]), 23.);
+
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let estimated = estimate(&staircase, &[3., 3., 3.], 100_000, &mut rng);
+        assert!((estimated - 23.).abs() < 0.2, "{}", estimated);
+    }
+
+    #[test]
+    fn hypervolume_many_objectives() {
+        let front = vec![vec![0.; 5], vec![0.5; 5]];
+        let hv = hypervolume(&front, &[1.; 5]);
+
+        assert!((hv - 1.).abs() < 1e-9, "{}", hv);
+
+        let front = vec![vec![0.5, 0., 0., 0., 0.], vec![0., 0.5, 0., 0., 0.]];
+        let hv = hypervolume(&front, &[1.; 5]);
+
+        assert!((hv - 0.75).abs() < 0.01, "{}", hv);
+    }
+
+    #[test]
+    fn hypervolume_result() {
+        let res = values_result(
+            vec![(vec![1., 3.], 0.), (vec![3., 1.], 0.), (vec![0., 0.], 1.)],
+            vec![],
+        );
+
+        // The infeasible solution is ignored
+        assert_eq!(res.hypervolume(&[4., 4.]), 5.);
+
+        // The reference point bounds maximized objectives from below
+        let res = values_result(
+            vec![(vec![1., -3.], 0.), (vec![3., -1.], 0.)],
+            vec![Direction::Minimize, Direction::Maximize],
+        );
+
+        assert_eq!(res.iter().next().unwrap().values, vec![1., 3.]);
+        assert_eq!(res.hypervolume(&[4., 0.]), 9.);
+    }
+}
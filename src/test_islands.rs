@@ -0,0 +1,49 @@
+#[cfg(test)]
+mod test {
+    use crate::test::*;
+    use crate::*;
+
+    fn optimizer(island: usize) -> NSGAOptimizer<'static, CandidateF64> {
+        NSGAOptimizer::new(sch_meta()).seed(island as u64)
+    }
+
+    #[test]
+    fn islands() {
+        for topology in [Topology::Ring, Topology::FullyConnected] {
+            let run = || {
+                Islands::new(3, optimizer)
+                    .topology(topology)
+                    .migration(5, 2)
+                    .optimize(|_| Box::new(DefaultEvaluator::new(50)))
+            };
+
+            let res = run();
+
+            assert_eq!(res.termination, Termination::Evaluator);
+            assert!(res.solutions.len() > POPULATION_SIZE);
+            res.iter().for_each(|x| {
+                assert_eq!(x.front, 0);
+                assert!(x.solution.val >= -0.1 && x.solution.val <= 2.1)
+            });
+
+            // Migration is synchronized, so seeded islands are reproducible
+            let vals = |r: OptimizationResult<CandidateF64>| -> Vec<f64> {
+                r.into_iter().map(|x| x.solution.val).collect()
+            };
+            assert_eq!(vals(res), vals(run()));
+        }
+    }
+
+    #[test]
+    fn islands_migrant_evaluations() {
+        let evaluations = |migrants| {
+            Islands::new(3, optimizer)
+                .migration(5, migrants)
+                .optimize(|_| Box::new(MaxGenerations::new(20)))
+                .evaluations
+        };
+
+        // Migrants are evaluated on their home island only
+        assert_eq!(evaluations(5), evaluations(0));
+    }
+}
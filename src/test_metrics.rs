@@ -0,0 +1,172 @@
+#[cfg(test)]
+mod test {
+    use crate::metrics::*;
+    use crate::test::*;
+    use crate::*;
+
+    // The true front of SCH: f2 = (sqrt(f1) - 2)^2 for f1 in [0, 4]
+    fn sch_front(n: usize) -> Vec<Vec<f64>> {
+        (0..n)
+            .map(|i| {
+                let x = 2. * i as f64 / (n - 1) as f64;
+                vec![x * x, (x - 2.) * (x - 2.)]
+            })
+            .collect()
+    }
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn convergence() {
+        let reference = vec![vec![0., 1.], vec![1., 0.]];
+
+        assert_eq!(generational_distance(&reference, &reference), 0.);
+        assert_eq!(inverted_generational_distance(&reference, &reference), 0.);
+
+        let front = vec![vec![0., 2.]];
+        assert!(close(generational_distance(&front, &reference), 1.));
+        assert!(close(
+            inverted_generational_distance(&front, &reference),
+            (1. + 5f64.sqrt()) / 2.
+        ));
+        assert!(close(
+            inverted_generational_distance_plus(&front, &reference),
+            1.5
+        ));
+
+        // A point dominating the whole reference front is at no distance from it
+        let front = vec![vec![0., 0.]];
+        assert_eq!(inverted_generational_distance_plus(&front, &reference), 0.);
+        assert!(inverted_generational_distance(&front, &reference) > 0.);
+
+        let empty: Vec<Vec<f64>> = vec![];
+        assert_eq!(generational_distance(&empty, &reference), f64::INFINITY);
+        assert_eq!(
+            inverted_generational_distance(&empty, &reference),
+            f64::INFINITY
+        );
+    }
+
+    #[test]
+    fn diversity() {
+        let even = vec![vec![0., 2.], vec![1., 1.], vec![2., 0.]];
+        assert_eq!(spacing(&even), 0.);
+        assert_eq!(spread(&even, &even), 0.);
+        assert!(close(maximum_spread(&even), 8f64.sqrt()));
+
+        // Closest neighbours at 2, 2 and 4 in Manhattan distance
+        let uneven = vec![vec![0., 3.], vec![1., 2.], vec![3., 0.]];
+        assert!(close(spacing(&uneven), (4. / 3f64).sqrt()));
+
+        // A single point in the middle misses both extremes
+        let middle = vec![vec![1., 1.]];
+        assert_eq!(spacing(&middle), 0.);
+        assert!(close(spread(&middle, &even), 1.));
+
+        // Both ends reached, one gap twice as large as the other
+        let gap = vec![vec![0., 3.], vec![1., 2.], vec![3., 0.]];
+        let d = 2f64.sqrt();
+        let mean = (d + 2. * d) / 2.;
+        let expected = (0.5 * d + 0.5 * d) / (2. * mean);
+        assert!(close(spread(&gap, &gap), expected));
+    }
+
+    #[test]
+    fn spread_directions() {
+        // Results are built from values oriented for minimization
+        let front = |values: Vec<Vec<f64>>| {
+            values_result(
+                values
+                    .into_iter()
+                    .map(|v| (vec![v[0], -v[1]], 0.))
+                    .collect(),
+                vec![Direction::Minimize, Direction::Maximize],
+            )
+        };
+
+        // The first objective is minimized, the second one maximized
+        let reference = vec![vec![0., 0.], vec![0.5, 0.5], vec![1., 1.]];
+        assert_eq!(spread(&front(reference.clone()), &reference), 0.);
+
+        // Missing the best end of the maximized objective
+        let partial = front(vec![vec![0., 0.], vec![0.5, 0.5]]);
+        assert!(close(spread(&partial, &reference), 0.5));
+    }
+
+    #[test]
+    fn metrics_sch() {
+        let reference = sch_front(1001);
+        let front = sch_front(11);
+
+        // A subset of the true front has no generational distance
+        // but covers it only partially
+        assert!(close(generational_distance(&front, &reference), 0.));
+        assert!(inverted_generational_distance(&front, &reference) > 0.);
+        assert!(close(maximum_spread(&front), 32f64.sqrt()));
+
+        let res = NSGAOptimizer::new(ParamsF64 {
+            population_size: 50,
+            mutation_odds: &Ratio(1, 1),
+            ..sch_meta()
+        })
+        .seed(0)
+        .optimize(Box::new(DefaultEvaluator::new(100)));
+
+        assert!(generational_distance(&res, &reference) < 0.01);
+        assert!(inverted_generational_distance(&res, &reference) < 0.1);
+        assert!(inverted_generational_distance_plus(&res, &reference) < 0.1);
+        assert!(spread(&res, &reference) < 1.);
+    }
+
+    #[test]
+    fn metrics_known_values() {
+        use crate::hypervolume::hypervolume;
+        use crate::problems::*;
+
+        // The areas dominated by the analytic fronts: 1 - ∫(1 - √x) = 2/3
+        // for ZDT1 and 1 - ∫(1 - x²) = 1/3 for ZDT2
+        let zdt1 = zdt1(30).front(1001);
+        let zdt2 = zdt2(30).front(1001);
+
+        assert!((hypervolume(&zdt1, &[1., 1.]) - 2. / 3.).abs() < 1e-3);
+        assert!((hypervolume(&zdt2, &[1., 1.]) - 1. / 3.).abs() < 1e-3);
+        assert!((hypervolume(&zdt1, &[1.1, 1.1]) - (0.1 + 2. / 3. + 0.11)).abs() < 1e-3);
+
+        // A uniform sample of the front is at no distance from itself
+        assert_eq!(generational_distance(&zdt1, &zdt1), 0.);
+        assert_eq!(inverted_generational_distance(&zdt2, &zdt2), 0.);
+
+        // Shifting the linear DTLZ1 front along its normal by (d, d)
+        // moves every point exactly d√2 away from it
+        let reference = dtlz1(2, 6).front(101);
+        let d = 0.01;
+        let shifted: Vec<Vec<f64>> = reference
+            .iter()
+            .map(|p| p.iter().map(|v| v + d).collect())
+            .collect();
+
+        assert!(close(
+            generational_distance(&shifted, &reference),
+            d * 2f64.sqrt()
+        ));
+        assert!(close(
+            inverted_generational_distance(&shifted, &reference),
+            d * 2f64.sqrt()
+        ));
+        assert!(close(
+            inverted_generational_distance_plus(&shifted, &reference),
+            d * 2f64.sqrt()
+        ));
+    }
+
+    #[test]
+    fn metrics_result() {
+        let res = values_result(vec![(vec![0., 1.], 0.), (vec![0., 0.], 1.)], vec![]);
+
+        // The infeasible solution is ignored
+        assert_eq!(generational_distance(&res, &vec![vec![0., 1.]]), 0.);
+        assert_eq!(maximum_spread(&res), 0.);
+    }
+}
@@ -0,0 +1,22 @@
+#[cfg(test)]
+mod test {
+    use crate::test::*;
+    use crate::*;
+
+    #[test]
+    fn moead_sch() {
+        for scalarization in [
+            Scalarization::Tchebycheff,
+            Scalarization::PBI { penalty: 5. },
+        ] {
+            let res = MOEADOptimizer::new(sch_meta())
+                .seed(0)
+                .scalarization(scalarization)
+                .neighbourhood(10)
+                .optimize(Box::new(DefaultEvaluator::new(100)));
+
+            assert_eq!(res.termination, Termination::Evaluator);
+            assert_sch_front(&res);
+        }
+    }
+}
@@ -0,0 +1,262 @@
+#[cfg(test)]
+mod test {
+    use crate::metrics::generational_distance;
+    use crate::problems::*;
+    use crate::*;
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    fn values<S: Solution>(problem: &Problem<S>, sol: &S) -> Vec<f64> {
+        (problem.values().unwrap())(sol)
+    }
+
+    // Objective values of random solutions with the variables set by `optimal`
+    fn optima(problem: &mut Problem<RealVector>, optimal: impl Fn(&mut [f64])) -> Vec<Vec<f64>> {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+
+        (0..100)
+            .map(|_| {
+                let mut sol = problem.random_solution(&mut rng);
+                optimal(&mut sol.x);
+
+                values(problem, &sol)
+            })
+            .collect()
+    }
+
+    // The optima dominated by none of the others, those of the parts of a
+    // disconnected front
+    fn pareto_optimal(optima: Vec<Vec<f64>>) -> Vec<Vec<f64>> {
+        optima
+            .iter()
+            .filter(|a| !optima.iter().any(|b| sorting::dominates(b, a)))
+            .cloned()
+            .collect()
+    }
+
+    // The normalized variable of WFG7 to WFG9 whose b_param transformation
+    // with the average `u` of the variables it depends on is 0.35
+    fn wfg_depending(u: f64) -> f64 {
+        let a = 0.98 / 49.98;
+        let v = a - (1. - 2. * u) * ((0.5 - u).floor() + a).abs();
+
+        0.35_f64.powf(1. / (0.02 + 49.98 * v))
+    }
+
+    #[test]
+    fn zdt_optima() {
+        for mut problem in [zdt1(30), zdt2(30), zdt4(10), zdt6(10)] {
+            let optima = optima(&mut problem, |x| x[1..].fill(0.));
+            let gd = generational_distance(&optima, &problem.front(10_001));
+
+            assert!(gd < 1e-3, "{}: {}", problem.name(), gd);
+        }
+
+        // Only five regions of the first variable are optimal for ZDT3
+        let mut problem = zdt3(30);
+        let front = problem.front(10_001);
+        let points = optima(&mut problem, |x| {
+            let regions = [0.04, 0.22, 0.43, 0.63, 0.84];
+            x[0] = regions[(x[0] * 5.) as usize % 5];
+            x[1..].fill(0.);
+        });
+        let gd = generational_distance(&points, &front);
+        assert!(gd < 1e-3, "{}: {}", problem.name(), gd);
+
+        let mut problem = zdt5(11);
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let mut sol = problem.random_solution(&mut rng);
+        sol.bits[30..].iter_mut().for_each(|b| *b = true);
+
+        let front = problem.front(0);
+        assert_eq!(front.len(), 31);
+        assert!(front.contains(&values(&problem, &sol)));
+    }
+
+    #[test]
+    fn dtlz_optima() {
+        for mut problem in [
+            dtlz1(3, 7),
+            dtlz2(3, 12),
+            dtlz3(3, 12),
+            dtlz4(3, 12),
+            dtlz5(3, 12),
+        ] {
+            let optima = optima(&mut problem, |x| x[2..].fill(0.5));
+            let gd = generational_distance(&optima, &problem.front(5_000));
+
+            assert!(gd < 0.05, "{}: {}", problem.name(), gd);
+        }
+
+        let mut problem = dtlz6(3, 12);
+        let points = optima(&mut problem, |x| x[2..].fill(0.));
+        let gd = generational_distance(&points, &problem.front(5_000));
+        assert!(gd < 0.05, "{}: {}", problem.name(), gd);
+
+        let mut problem = dtlz7(3, 22);
+        let points = pareto_optimal(optima(&mut problem, |x| x[2..].fill(0.)));
+        let gd = generational_distance(&points, &problem.front(5_000));
+        assert!(points.len() >= 10, "{}", points.len());
+        assert!(gd < 0.05, "{}: {}", problem.name(), gd);
+
+        // Linear and spherical fronts
+        for f in dtlz1(3, 7).front(91) {
+            assert!((f.iter().sum::<f64>() - 0.5).abs() < 1e-9);
+        }
+
+        for f in dtlz2(3, 12).front(91) {
+            assert!((f.iter().map(|v| v * v).sum::<f64>() - 1.).abs() < 1e-9);
+        }
+
+        assert_eq!(dtlz2(3, 12).front(91).len(), 91);
+        assert!(dtlz7(3, 22).front(1000).len() > 100);
+    }
+
+    #[test]
+    fn wfg_optima() {
+        // The flat region of WFG1 turns even rounding errors of the optimal
+        // distance variables into large objective errors, these stay exact
+        let (k, l) = (6, 2);
+        let n = k + l;
+        let scale = |i: usize| 2. * (i + 1) as f64;
+
+        // The optimal distance variables are at 0.35 of their ranges
+        let separable = |x: &mut [f64]| {
+            for (i, x) in x.iter_mut().enumerate().skip(k) {
+                *x = 0.35 * scale(i);
+            }
+        };
+
+        for mut problem in [
+            wfg1(2, k, l),
+            wfg3(2, k, l),
+            wfg4(2, k, l),
+            wfg5(2, k, l),
+            wfg6(2, k, l),
+            wfg7(2, k, l),
+        ] {
+            let optima = optima(&mut problem, separable);
+            let gd = generational_distance(&optima, &problem.front(2_000));
+
+            assert!(gd < 0.01, "{}: {}", problem.name(), gd);
+        }
+
+        let mut problem = wfg2(2, k, l);
+        let points = pareto_optimal(optima(&mut problem, separable));
+        let gd = generational_distance(&points, &problem.front(2_000));
+        assert!(points.len() >= 10, "{}", points.len());
+        assert!(gd < 0.01, "{}: {}", problem.name(), gd);
+
+        // The distance variables of WFG8 depend on all the variables before them
+        let mut problem = wfg8(2, k, l);
+        let points = optima(&mut problem, |x| {
+            for i in k..n {
+                let u = (0..i).map(|j| x[j] / scale(j)).sum::<f64>() / i as f64;
+                x[i] = wfg_depending(u) * scale(i);
+            }
+        });
+        let gd = generational_distance(&points, &problem.front(2_000));
+        assert!(gd < 0.01, "{}: {}", problem.name(), gd);
+
+        // Those of WFG9 on all the variables after them, but the last one
+        let mut problem = wfg9(2, k, l);
+        let points = optima(&mut problem, |x| {
+            x[n - 1] = 0.35 * scale(n - 1);
+            for i in (k..n - 1).rev() {
+                let u = (i + 1..n).map(|j| x[j] / scale(j)).sum::<f64>() / (n - i - 1) as f64;
+                x[i] = wfg_depending(u) * scale(i);
+            }
+        });
+        let gd = generational_distance(&points, &problem.front(2_000));
+        assert!(gd < 0.01, "{}: {}", problem.name(), gd);
+
+        for problem in [wfg4(3, k, l), wfg9(3, k, l)] {
+            for f in problem.front(91) {
+                let radius: f64 = f
+                    .iter()
+                    .enumerate()
+                    .map(|(i, v)| (v / (2. * (i + 1) as f64)).powi(2))
+                    .sum();
+
+                assert!((radius - 1.).abs() < 1e-9);
+            }
+        }
+
+        // Random solutions are never better than the front
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        for mut problem in [wfg2(3, k, l), wfg8(3, k, l), wfg9(3, k, l)] {
+            let front = problem.front(1000);
+            assert!(!front.is_empty());
+
+            for _ in 0..100 {
+                let sol = problem.random_solution(&mut rng);
+                let vals = values(&problem, &sol);
+
+                assert!(!front.iter().any(|f| sorting::dominates(&vals, f)));
+            }
+        }
+    }
+
+    #[test]
+    fn problem_evaluations() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+
+        let problem = Problem::new(
+            "Count".to_string(),
+            3,
+            move |s: &RealVector| {
+                counter.fetch_add(1, Ordering::SeqCst);
+                vec![s.x[0], 1. - s.x[0], s.x[0] * s.x[0]]
+            },
+            |rng| RealVector {
+                x: vec![rng.gen_range(0.0..1.0)],
+                bounds: vec![(0., 1.)],
+            },
+            |_| vec![],
+        );
+
+        // Every solution is evaluated once, not once per objective
+        let res = NSGAOptimizer::new(problem.population_size(10))
+            .seed(0)
+            .optimize(Box::new(MaxGenerations::new(5)));
+
+        assert_eq!(calls.load(Ordering::SeqCst), res.evaluations);
+    }
+
+    #[test]
+    fn nsga_zdt1() {
+        let problem = zdt1(30);
+        let front = problem.front(1000);
+
+        let res = NSGAOptimizer::new(problem)
+            .seed(0)
+            .optimize(Box::new(MaxGenerations::new(150)));
+
+        let igd = metrics::inverted_generational_distance(&res, &front);
+        assert!(igd < 0.01, "{}", igd);
+    }
+
+    #[test]
+    fn nsga3_dtlz2() {
+        let front = dtlz2(5, 14).front(1000);
+
+        let igd = |survival| {
+            let res = NSGAOptimizer::new(dtlz2(5, 14).population_size(72))
+                .seed(0)
+                .survival(survival)
+                .optimize(Box::new(MaxGenerations::new(150)));
+
+            metrics::inverted_generational_distance(&res, &front)
+        };
+
+        // 70 reference directions for five objectives
+        let nsga3 = igd(Survival::ReferencePoints { divisions: 4 });
+        let nsga2 = igd(Survival::CrowdingDistance);
+
+        assert!(nsga3 < 0.3, "{}", nsga3);
+        assert!(nsga3 < nsga2, "{} >= {}", nsga3, nsga2);
+    }
+}
@@ -2,44 +2,430 @@
 mod test {
     use crate::test::*;
     use crate::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
 
-    pub struct SchObjective1 {}
+    struct CountingObjective {
+        inner: Box<dyn Objective<CandidateF64>>,
+        count: Arc<AtomicUsize>,
+    }
 
-    impl Objective<CandidateF64> for SchObjective1 {
+    impl Objective<CandidateF64> for CountingObjective {
         fn value(&self, candidate: &CandidateF64) -> f64 {
-            candidate.val * candidate.val
+            self.count.fetch_add(1, Ordering::SeqCst);
+            self.inner.value(candidate)
         }
     }
 
-    pub struct SchObjective2 {}
+    fn meta() -> ParamsF64<'static> {
+        ParamsF64 {
+            mutation_odds: &Ratio(1, 1),
+            ..sch_meta()
+        }
+    }
 
-    impl Objective<CandidateF64> for SchObjective2 {
+    #[test]
+    fn sch() {
+        let mut optimizer = NSGAOptimizer::new(meta());
+        optimizer
+            .optimize(Box::new(DefaultEvaluator::new(100)))
+            .into_iter()
+            .for_each(|x| {
+                println!("{}", x.solution.val);
+                assert!(x.solution.val >= -0.1 && x.solution.val <= 2.1)
+            });
+    }
+
+    // An SCH objective, infinite for negative x
+    struct SchInfinite(Box<dyn Objective<CandidateF64>>);
+
+    impl Objective<CandidateF64> for SchInfinite {
         fn value(&self, candidate: &CandidateF64) -> f64 {
-            let x = candidate.val - 2.;
-            x * x
+            if candidate.val < 0. {
+                f64::INFINITY
+            } else {
+                self.0.value(candidate)
+            }
         }
     }
 
     #[test]
-    fn sch() {
-        let objectives: Vec<Box<dyn Objective<CandidateF64>>> =
-            vec![Box::new(SchObjective1 {}), Box::new(SchObjective2 {})];
+    fn sch_infinite() {
+        let meta = || {
+            let mut meta = meta();
+            meta.objectives = meta
+                .objectives
+                .into_iter()
+                .map(|o| Box::new(SchInfinite(o)) as Box<dyn Objective<CandidateF64>>)
+                .collect();
+            meta
+        };
 
-        let meta = ParamsF64 {
-            population_size: POPULATION_SIZE,
-            crossover_odds: &CROSSOVER_ODDS,
-            mutation_odds: &Ratio(1, 1),
-            objectives,
-            constraints: vec![],
-            val_range: -55.0..56.0,
+        // Fronts of infinite values get NaN crowding distances
+        // and can't be normalized, neither must panic
+        for survival in [
+            Survival::CrowdingDistance,
+            Survival::ReferencePoints { divisions: 19 },
+        ] {
+            for seed in 0..10 {
+                let res = NSGAOptimizer::new(meta())
+                    .seed(seed)
+                    .survival(survival.clone())
+                    .archive(ParetoArchive::new().capacity(5))
+                    .optimize(Box::new(MaxGenerations::new(20)));
+
+                assert!(res.iter().all(|x| x.values[0].is_finite()));
+            }
+        }
+    }
+
+    #[test]
+    fn sch_seeded() {
+        let run = |seed| {
+            NSGAOptimizer::new(meta())
+                .seed(seed)
+                .optimize(Box::new(DefaultEvaluator::new(50)))
+                .into_iter()
+                .map(|x| x.solution.val)
+                .collect::<Vec<_>>()
         };
 
-        let mut optimizer = NSGAOptimizer::new(meta);
-        optimizer
+        assert_eq!(run(42), run(42));
+    }
+
+    #[test]
+    fn sch_evaluations() {
+        const GENERATIONS: usize = 5;
+
+        let count = Arc::new(AtomicUsize::new(0));
+        let mut meta = meta();
+        meta.objectives = meta
+            .objectives
+            .into_iter()
+            .map(|inner| {
+                Box::new(CountingObjective {
+                    inner,
+                    count: count.clone(),
+                }) as Box<dyn Objective<CandidateF64>>
+            })
+            .collect();
+
+        let res = NSGAOptimizer::new(meta).optimize(Box::new(MaxGenerations::new(GENERATIONS)));
+
+        assert_eq!(res.termination, Termination::Evaluator);
+        assert_eq!(res.generations, GENERATIONS);
+        assert_eq!(res.evaluations, POPULATION_SIZE * (GENERATIONS + 1));
+
+        // Every objective is evaluated once per new solution:
+        // the initial population plus one offspring population per generation
+        assert_eq!(
+            count.load(Ordering::SeqCst),
+            2 * POPULATION_SIZE * (GENERATIONS + 1)
+        );
+    }
+
+    // x >= 1
+    struct SchConstraint {}
+
+    impl Constraint<CandidateF64> for SchConstraint {
+        fn violation(&self, candidate: &CandidateF64) -> f64 {
+            1. - candidate.val
+        }
+    }
+
+    #[test]
+    fn sch_constrained() {
+        let mut meta = meta();
+        meta.constraints = vec![Box::new(SchConstraint {})];
+
+        NSGAOptimizer::new(meta)
             .optimize(Box::new(DefaultEvaluator::new(100)))
+            .into_iter()
             .for_each(|x| {
-                println!("{}", x.val);
-                assert!(x.val >= -0.1 && x.val <= 2.1)
+                assert!(x.is_feasible());
+                assert!(x.solution.val >= 1. && x.solution.val <= 2.1)
             });
     }
+
+    #[test]
+    fn sch_reference_points() {
+        NSGAOptimizer::new(meta())
+            .survival(Survival::ReferencePoints { divisions: 19 })
+            .optimize(Box::new(DefaultEvaluator::new(100)))
+            .into_iter()
+            .for_each(|x| assert!(x.solution.val >= -0.1 && x.solution.val <= 2.1));
+    }
+
+    #[test]
+    fn sch_no_duplicates() {
+        NSGAOptimizer::new(meta())
+            .seed(0)
+            .duplicates(Duplicates::Objectives { tolerance: 1e-3 })
+            .observer(|g: &Generation<CandidateF64>| {
+                let pop = &g.population;
+
+                for (i, a) in pop.iter().enumerate() {
+                    assert!(pop[..i].iter().all(|b| a
+                        .values
+                        .iter()
+                        .zip(b.values)
+                        .any(|(v1, v2)| (v1 - v2).abs() > 1e-3)));
+                }
+
+                g.index == 20
+            })
+            .optimize(Box::new(DefaultEvaluator::new(100)));
+    }
+
+    #[test]
+    fn sch_steady_state() {
+        let mut evaluations = vec![];
+
+        let res = NSGAOptimizer::new(meta())
+            .seed(0)
+            .steady_state(2)
+            .observer(|g: &Generation<CandidateF64>| {
+                let values: Vec<_> = g.population.iter().map(|i| i.values).collect();
+                let fronts: Vec<_> = g.population.iter().map(|i| i.front).collect();
+
+                // Incremental updates keep the same fronts as a full sort
+                assert_eq!(g.population.len(), POPULATION_SIZE);
+                assert_eq!(fronts, crate::sorting::non_dominated_sort(&values));
+
+                evaluations.push(g.evaluations);
+                false
+            })
+            .optimize(Box::new(DefaultEvaluator::new(300)));
+
+        assert!(evaluations.len() > 300);
+        assert!(evaluations.windows(2).all(|w| w[1] == w[0] + 2));
+        res.iter()
+            .for_each(|x| assert!(x.solution.val >= -0.1 && x.solution.val <= 2.1));
+    }
+
+    #[test]
+    fn sch_ask_tell() {
+        const GENERATIONS: usize = 10;
+
+        let objectives = meta().objectives;
+        let mut optimizer = NSGAOptimizer::new(meta()).seed(7);
+
+        for _ in 0..=GENERATIONS {
+            let values = optimizer
+                .ask()
+                .iter()
+                .map(|x| objectives.iter().map(|obj| obj.value(x)).collect())
+                .collect();
+
+            optimizer.tell(values);
+        }
+
+        let mut told: Vec<_> = optimizer.solutions().map(|x| x.val).collect();
+        told.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        // The same run driven by `optimize`
+        let optimized: Vec<_> = NSGAOptimizer::new(meta())
+            .seed(7)
+            .optimize(Box::new(MaxGenerations::new(GENERATIONS)))
+            .into_iter()
+            .map(|x| x.solution.val)
+            .collect();
+
+        // Solutions are sorted by objective values, i.e. by `val` here
+        assert!(!told.is_empty());
+        assert_eq!(told, optimized);
+    }
+
+    #[test]
+    fn sch_observer() {
+        let mut seen = vec![];
+
+        let res = NSGAOptimizer::new(meta())
+            .observer(|g: &Generation<CandidateF64>| {
+                assert_eq!(g.population.len(), POPULATION_SIZE);
+                assert!(g.population.windows(2).all(|w| w[0].front <= w[1].front));
+
+                seen.push((g.index, g.evaluations));
+                g.index == 3
+            })
+            .optimize(Box::new(DefaultEvaluator::new(100)));
+
+        assert_eq!(res.termination, Termination::Observer);
+
+        assert_eq!(
+            seen,
+            (0..=3)
+                .map(|i| (i, POPULATION_SIZE * (i + 1)))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn sch_resume() {
+        let path = std::env::temp_dir().join(format!("nsga-sch-{}.checkpoint", std::process::id()));
+
+        let uninterrupted: Vec<_> = NSGAOptimizer::new(meta())
+            .seed(3)
+            .optimize(Box::new(MaxGenerations::new(10)))
+            .into_iter()
+            .map(|x| x.solution.val)
+            .collect();
+
+        // Crash right after the checkpoint at generation 5
+        let _ = NSGAOptimizer::new(meta())
+            .seed(3)
+            .checkpoint(&path, 5)
+            .optimize(Box::new(MaxGenerations::new(7)));
+
+        let resumed: Vec<_> = NSGAOptimizer::resume(meta(), &path)
+            .unwrap()
+            .optimize(Box::new(MaxGenerations::new(10)))
+            .into_iter()
+            .map(|x| x.solution.val)
+            .collect();
+
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(uninterrupted, resumed);
+        assert!(!path.with_extension("checkpoint.tmp").exists());
+    }
+
+    // Terminates after a given number of calls
+    #[cfg(feature = "serde")]
+    struct Patience(usize);
+
+    #[cfg(feature = "serde")]
+    impl Evaluator for Patience {
+        fn can_terminate(&mut self, _: &Progress) -> bool {
+            self.0 = self.0.saturating_sub(1);
+            self.0 == 0
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn sch_resume_evaluator() {
+        let path =
+            std::env::temp_dir().join(format!("nsga-patience-{}.checkpoint", std::process::id()));
+
+        let uninterrupted = NSGAOptimizer::new(meta()).optimize(Box::new(Patience(10)));
+
+        let _ = NSGAOptimizer::new(meta())
+            .checkpoint(&path, 5)
+            .optimize(Box::new(MaxGenerations::new(7)));
+
+        let resumed = NSGAOptimizer::resume(meta(), &path)
+            .unwrap()
+            .optimize(Box::new(Patience(10)));
+
+        std::fs::remove_file(&path).unwrap();
+
+        // The evaluator state is not saved, so it starts over after resuming
+        assert_eq!(resumed.generations, uninterrupted.generations + 5);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn sch_checkpoint_error() {
+        let path = std::env::temp_dir()
+            .join(format!("nsga-missing-{}", std::process::id()))
+            .join("sch.checkpoint");
+
+        let mut optimizer = NSGAOptimizer::new(meta()).checkpoint(&path, 5);
+        let res = optimizer.optimize(Box::new(MaxGenerations::new(10)));
+
+        assert_eq!(res.termination, Termination::Checkpoint);
+        assert_eq!(res.generations, 0);
+        assert!(optimizer.checkpoint_error().is_some());
+    }
+
+    #[test]
+    #[should_panic(expected = "population size must be greater than zero")]
+    fn sch_empty_population() {
+        let meta = ParamsF64 {
+            population_size: 0,
+            ..meta()
+        };
+
+        NSGAOptimizer::new(meta).optimize(Box::new(MaxGenerations::new(10)));
+    }
+
+    #[test]
+    #[should_panic(expected = "expected a value for every objective")]
+    fn sch_tell_objectives() {
+        let mut optimizer = NSGAOptimizer::new(meta());
+        let batch = optimizer.ask();
+
+        optimizer.tell(batch.iter().map(|c| vec![c.val * c.val]).collect());
+    }
+
+    #[test]
+    fn sch_warm_start() {
+        let known = |val| CandidateF64 {
+            val,
+            range_start: -55.,
+            range_end: 56.,
+        };
+
+        let mut optimizer = NSGAOptimizer::new(meta()).warm_start(vec![known(0.), known(2.)]);
+        let batch = optimizer.ask();
+
+        assert_eq!(batch.len(), POPULATION_SIZE);
+        assert_eq!(batch[0].val, 0.);
+        assert_eq!(batch[1].val, 2.);
+
+        // Both extremes of the front survive from the very beginning
+        let res = NSGAOptimizer::new(meta())
+            .warm_start(vec![known(0.), known(2.)])
+            .optimize(Box::new(MaxGenerations::new(0)));
+
+        let vals: Vec<_> = res.iter().map(|x| x.solution.val).collect();
+        assert!(vals.contains(&0.) && vals.contains(&2.));
+    }
+
+    // -(x - 2)^2, maximized
+    struct SchMaximized {}
+
+    impl Objective<CandidateF64> for SchMaximized {
+        fn value(&self, candidate: &CandidateF64) -> f64 {
+            -SchObjective2 {}.value(candidate)
+        }
+
+        fn direction(&self) -> Direction {
+            Direction::Maximize
+        }
+
+        fn good_enough(&self, val: f64) -> bool {
+            val > 1.
+        }
+    }
+
+    struct NonPositive(usize);
+
+    impl Evaluator for NonPositive {
+        fn can_terminate(&mut self, progress: &Progress) -> bool {
+            assert_eq!(progress.directions[1], Direction::Maximize);
+            assert!(progress.population.iter().all(|v| v[1] <= 0.));
+            progress.generation >= self.0
+        }
+    }
+
+    #[test]
+    fn sch_maximized() {
+        let mut meta = meta();
+        meta.objectives[1] = Box::new(SchMaximized {});
+
+        let res = NSGAOptimizer::new(meta)
+            .seed(0)
+            .optimize(Box::new(NonPositive(100)));
+
+        // Natural values are reported, and they're never good enough
+        assert_eq!(res.termination, Termination::Evaluator);
+
+        for x in res.iter() {
+            assert!(x.solution.val >= -0.1 && x.solution.val <= 2.1);
+            assert!(x.values[1] <= 0.);
+        }
+    }
 }
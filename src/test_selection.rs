@@ -0,0 +1,106 @@
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use rand::{RngCore, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+
+    fn sol() -> CandidateF64 {
+        CandidateF64 {
+            val: 0.,
+            range_start: 0.,
+            range_end: 1.,
+        }
+    }
+
+    fn individual(
+        sol: &CandidateF64,
+        violation: f64,
+        front: usize,
+        distance: f64,
+    ) -> Individual<'_, CandidateF64> {
+        Individual {
+            solution: sol,
+            values: &[],
+            violation,
+            front,
+            distance,
+        }
+    }
+
+    fn counts(
+        selection: &mut impl Selection<CandidateF64>,
+        pop: &[Individual<CandidateF64>],
+    ) -> Vec<usize> {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let mut counts = vec![0; pop.len()];
+
+        for _ in 0..1000 {
+            counts[selection.select(pop, &mut rng)] += 1;
+        }
+
+        counts
+    }
+
+    #[test]
+    fn tournaments() {
+        let s = sol();
+        let pop = vec![
+            individual(&s, 0., 0, 1.),
+            individual(&s, 0., 0, f64::INFINITY),
+            individual(&s, 0., 1, f64::INFINITY),
+            individual(&s, 2., 2, f64::INFINITY),
+            individual(&s, 1., 2, 0.),
+        ];
+
+        // Large tournaments almost always include the best member
+        let c = counts(&mut CrowdedTournament { size: 30 }, &pop);
+        assert!(c[1] > 990, "{:?}", c);
+
+        // Lower violation wins over the crowding distance
+        let c = counts(&mut ConstrainedTournament { size: 30 }, &pop[3..]);
+        assert_eq!(c, vec![0, 1000]);
+
+        // The worst member only wins against itself in a binary tournament,
+        // i.e. in 1 out of 25 cases
+        let c = counts(&mut ConstrainedTournament::default(), &pop);
+        assert!(c[3] < 80, "{:?}", c);
+        assert!(c.iter().all(|x| *x > 0), "{:?}", c);
+    }
+
+    #[test]
+    fn random_selection() {
+        let s = sol();
+        let pop: Vec<_> = (0..5).map(|i| individual(&s, 0., i, 0.)).collect();
+
+        let c = counts(&mut RandomSelection, &pop);
+        assert!(c.iter().all(|x| *x > 150), "{:?}", c);
+    }
+
+    #[test]
+    fn closure_selection() {
+        let meta = ParamsF64 {
+            population_size: 10,
+            crossover_odds: &crate::test::CROSSOVER_ODDS,
+            mutation_odds: &crate::test::MUTATION_ODDS,
+            objectives: vec![],
+            constraints: vec![],
+            val_range: 0.0..1.0,
+        };
+
+        let mut calls = 0;
+        let mut optimizer = NSGAOptimizer::new(meta).seed(0).selection(
+            |pop: &[Individual<CandidateF64>], _: &mut dyn RngCore| {
+                calls += 1;
+                pop.len() - 1
+            },
+        );
+
+        // The initial population is random, the next batch is the offspring
+        let batch = optimizer.ask();
+        optimizer.tell(vec![vec![]; batch.len()]);
+        optimizer.ask();
+        drop(optimizer);
+
+        assert_eq!(calls, 10);
+    }
+}
@@ -0,0 +1,197 @@
+#[cfg(test)]
+mod test {
+    use crate::sorting::*;
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+    use std::collections::{HashMap, HashSet};
+
+    // The original HashMap-based fast non-dominated sort
+    fn reference_sort(values: &[Vec<f64>]) -> Vec<usize> {
+        reference_sort_by(values.len(), |i, j| dominates(&values[i], &values[j]))
+    }
+
+    fn reference_sort_by(len: usize, dominates: impl Fn(usize, usize) -> bool) -> Vec<usize> {
+        let mut dominates_set: HashMap<usize, HashSet<usize>> = HashMap::new();
+        let mut dominated_by: HashMap<usize, usize> = HashMap::new();
+        let mut fronts: Vec<HashSet<usize>> = vec![HashSet::new()];
+
+        for i in 0..len {
+            for j in i + 1..len {
+                let r = if dominates(i, j) {
+                    Some((i, j))
+                } else if dominates(j, i) {
+                    Some((j, i))
+                } else {
+                    None
+                };
+
+                if let Some((d, dby)) = r {
+                    dominates_set.entry(d).or_default().insert(dby);
+                    *dominated_by.entry(dby).or_insert(0) += 1;
+                }
+            }
+
+            if !dominated_by.contains_key(&i) {
+                fronts[0].insert(i);
+            }
+        }
+
+        let mut i = 0;
+        while !fronts[i].is_empty() {
+            let mut new_front = HashSet::new();
+
+            for id in fronts[i].iter() {
+                if let Some(set) = dominates_set.get(id) {
+                    for dominated_id in set.iter() {
+                        dominated_by.entry(*dominated_id).and_modify(|v| {
+                            if *v > 0 {
+                                *v -= 1
+                            }
+                        });
+
+                        if let None | Some(0) = dominated_by.get(dominated_id) {
+                            new_front.insert(*dominated_id);
+                        }
+                    }
+                }
+            }
+
+            i += 1;
+            fronts.push(new_front);
+        }
+
+        let mut ranks = vec![0; len];
+        for (fidx, f) in fronts.into_iter().enumerate() {
+            for id in f {
+                ranks[id] = fidx;
+            }
+        }
+
+        ranks
+    }
+
+    #[test]
+    fn sort_simple() {
+        let values = vec![
+            vec![1., 5.],
+            vec![2., 2.],
+            vec![3., 3.],
+            vec![5., 1.],
+            vec![4., 4.],
+            vec![2., 2.],
+        ];
+
+        assert_eq!(non_dominated_sort(&values), vec![0, 0, 1, 0, 2, 0]);
+    }
+
+    #[test]
+    fn sort_matches_reference() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+
+        for objectives in 1..=5 {
+            for _ in 0..50 {
+                let size = rng.gen_range(0..80);
+                // A small value range produces plenty of ties and duplicates
+                let max = rng.gen_range(2..20);
+
+                let values: Vec<Vec<f64>> = (0..size)
+                    .map(|_| {
+                        (0..objectives)
+                            .map(|_| rng.gen_range(0..max) as f64)
+                            .collect()
+                    })
+                    .collect();
+
+                assert_eq!(
+                    non_dominated_sort(&values),
+                    reference_sort(&values),
+                    "{:?}",
+                    values
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn sort_matches_reference_continuous() {
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+
+        for objectives in 2..=4 {
+            let values: Vec<Vec<f64>> = (0..500)
+                .map(|_| (0..objectives).map(|_| rng.gen_range(0.0..1.0)).collect())
+                .collect();
+
+            assert_eq!(non_dominated_sort(&values), reference_sort(&values));
+        }
+    }
+
+    #[test]
+    fn constrained_sort_matches_reference() {
+        let mut rng = ChaCha8Rng::seed_from_u64(2);
+
+        for _ in 0..50 {
+            let size = rng.gen_range(0..80);
+
+            let values: Vec<Vec<f64>> = (0..size)
+                .map(|_| (0..3).map(|_| rng.gen_range(0..10) as f64).collect())
+                .collect();
+
+            let violations: Vec<f64> = (0..size)
+                .map(|_| {
+                    if rng.gen_ratio(1, 2) {
+                        0.
+                    } else {
+                        rng.gen_range(1..5) as f64
+                    }
+                })
+                .collect();
+
+            // Deb's constraint-domination
+            let reference = reference_sort_by(size, |i, j| {
+                if violations[i] == 0. && violations[j] == 0. {
+                    dominates(&values[i], &values[j])
+                } else {
+                    violations[i] < violations[j]
+                }
+            });
+
+            assert_eq!(
+                constrained_non_dominated_sort(&values, &violations),
+                reference
+            );
+        }
+    }
+
+    #[test]
+    fn crowding() {
+        let front = vec![vec![1., 5.], vec![2., 3.], vec![4., 2.], vec![6., 1.]];
+
+        // (2, 3): (4 - 1) / 5 + (5 - 2) / 4
+        // (4, 2): (6 - 2) / 5 + (3 - 1) / 4
+        let expected = [f64::INFINITY, 1.35, 1.3, f64::INFINITY];
+
+        for (d, e) in crowding_distance(&front).into_iter().zip(expected) {
+            assert!(d == e || (d - e).abs() < 1e-12, "{} != {}", d, e);
+        }
+    }
+
+    #[test]
+    fn crowding_tiny_fronts() {
+        assert_eq!(crowding_distance::<Vec<f64>>(&[]), Vec::<f64>::new());
+        assert_eq!(crowding_distance(&[vec![1., 2.]]), vec![f64::INFINITY]);
+        assert_eq!(
+            crowding_distance(&[vec![1., 2.], vec![2., 1.]]),
+            vec![f64::INFINITY, f64::INFINITY]
+        );
+    }
+
+    #[test]
+    fn crowding_flat_objective() {
+        let front = vec![vec![1., 1.], vec![1., 2.], vec![1., 3.]];
+
+        assert_eq!(
+            crowding_distance(&front),
+            vec![f64::INFINITY, 1., f64::INFINITY]
+        );
+    }
+}
@@ -0,0 +1,34 @@
+#[cfg(test)]
+mod test {
+    use crate::test::*;
+    use crate::*;
+
+    #[test]
+    fn spea2_sch() {
+        let res = SPEA2Optimizer::new(sch_meta())
+            .seed(0)
+            .optimize(Box::new(DefaultEvaluator::new(100)));
+
+        assert_eq!(res.termination, Termination::Evaluator);
+        assert_sch_front(&res);
+    }
+
+    #[test]
+    fn algorithms() {
+        let mut algorithms: Vec<Box<dyn Algorithm<CandidateF64>>> = vec![
+            Box::new(NSGAOptimizer::new(sch_meta()).seed(0)),
+            Box::new(SPEA2Optimizer::new(sch_meta()).seed(0)),
+            Box::new(MOEADOptimizer::new(sch_meta()).seed(0)),
+        ];
+
+        let names: Vec<_> = algorithms.iter().map(|a| a.name()).collect();
+        assert_eq!(names, vec!["NSGA-II", "SPEA2", "MOEA/D"]);
+
+        for algorithm in algorithms.iter_mut() {
+            let res = algorithm.optimize(Box::new(DefaultEvaluator::new(50)));
+
+            assert!(!res.solutions.is_empty());
+            assert_eq!(algorithm.solutions().count(), res.solutions.len());
+        }
+    }
+}
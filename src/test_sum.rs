@@ -2,7 +2,7 @@
 mod test {
     use crate::test::*;
     use crate::*;
-    use rand::{thread_rng, Rng};
+    use rand::{Rng, RngCore};
 
     #[derive(Clone, Debug)]
     struct Candidate {
@@ -10,7 +10,7 @@ mod test {
     }
 
     impl Solution for Candidate {
-        fn crossover(&mut self, other: &mut Self) {
+        fn crossover(&mut self, other: &mut Self, _rng: &mut dyn RngCore) {
             let mut a = &mut self.indices;
             let mut b = &mut other.indices;
 
@@ -32,9 +32,7 @@ mod test {
             b.extend(a.drain(a_len..));
         }
 
-        fn mutate(&mut self) {
-            let mut rng = thread_rng();
-
+        fn mutate(&mut self, rng: &mut dyn RngCore) {
             for i in &mut self.indices {
                 if rng.gen_ratio(MUTATION_ODDS.0, MUTATION_ODDS.1) {
                     *i = if *i == 0 { 1 } else { 0 }
@@ -98,7 +96,7 @@ mod test {
             &Ratio(1, 1)
         }
 
-        fn random_solution(&mut self) -> Candidate {
+        fn random_solution(&mut self, _rng: &mut dyn RngCore) -> Candidate {
             let indices: Vec<isize> = (0..self.records_length).map(|_| 0).collect();
 
             Candidate { indices }
@@ -165,12 +163,84 @@ mod test {
             };
 
             let mut optimizer = NSGAOptimizer::new(meta);
-            let res = optimizer
-                .optimize(Box::new(DefaultEvaluator::new(500)))
-                .next()
-                .unwrap();
+            let res = optimizer.optimize(Box::new(DefaultEvaluator::new(500)));
 
-            assert_eq!(c.answer, res.indices);
+            // The answer is one of the Pareto optimal solutions
+            assert!(res.iter().any(|x| x.solution.indices == c.answer));
         }
     }
+
+    #[test]
+    fn sum_no_duplicates() {
+        let meta = Params {
+            records_length: 9,
+            objectives: vec![Box::new(SumObjective {
+                goal: 100.,
+                items: vec![90., 15., 1., 2., 20., 5., 30., 1., 1.],
+                toleration: 0.0,
+            })],
+            constraints: vec![],
+        };
+
+        let mut generations = 0;
+
+        NSGAOptimizer::new(meta)
+            .seed(0)
+            .duplicates(Duplicates::Equal(Box::new(
+                |a: &Candidate, b: &Candidate| a.indices == b.indices,
+            )))
+            .observer(|g: &Generation<Candidate>| {
+                let pop = &g.population;
+
+                for (i, a) in pop.iter().enumerate() {
+                    assert!(pop[..i]
+                        .iter()
+                        .all(|b| a.solution.indices != b.solution.indices));
+                }
+
+                generations += 1;
+                false
+            })
+            .optimize(Box::new(DefaultEvaluator::new(50)));
+
+        assert!(generations > 1);
+    }
+
+    #[test]
+    fn sum_small_space() {
+        // Only 8 distinct solutions for a population of 20,
+        // and `random_solution` always returns the same one
+        let meta = Params {
+            records_length: 3,
+            objectives: vec![Box::new(SumObjective {
+                goal: 100.,
+                items: vec![90., 15., 1.],
+                toleration: 0.0,
+            })],
+            constraints: vec![],
+        };
+
+        let mut generations = 0;
+
+        NSGAOptimizer::new(meta)
+            .duplicates(Duplicates::Equal(Box::new(
+                |a: &Candidate, b: &Candidate| a.indices == b.indices,
+            )))
+            .observer(|g: &Generation<Candidate>| {
+                let pop = &g.population;
+
+                assert!(!pop.is_empty() && pop.len() <= 8);
+                for (i, a) in pop.iter().enumerate() {
+                    assert!(pop[..i]
+                        .iter()
+                        .all(|b| a.solution.indices != b.solution.indices));
+                }
+
+                generations += 1;
+                false
+            })
+            .optimize(Box::new(MaxGenerations::new(20)));
+
+        assert_eq!(generations, 21);
+    }
 }
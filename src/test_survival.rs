@@ -0,0 +1,73 @@
+#[cfg(test)]
+mod test {
+    use crate::survival::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn directions() {
+        let dirs = reference_directions(3, 4);
+
+        // C(3 + 4 - 1, 4)
+        assert_eq!(dirs.len(), 15);
+
+        for d in dirs {
+            assert_eq!(d.len(), 3);
+            assert!((d.iter().sum::<f64>() - 1.).abs() < 1e-12);
+        }
+
+        assert_eq!(reference_directions(5, 6).len(), 210);
+    }
+
+    #[test]
+    fn niching() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+
+        // A linear front x + y = 1
+        let last: Vec<Vec<f64>> = (0..=10)
+            .map(|i| vec![i as f64 / 10., 1. - i as f64 / 10.])
+            .collect();
+        let selected: Vec<Vec<f64>> = vec![];
+
+        let mut picked =
+            select_by_reference(&selected, &last, 3, &reference_directions(2, 2), &mut rng);
+        picked.sort_unstable();
+
+        assert_eq!(picked, vec![0, 5, 10]);
+    }
+
+    #[test]
+    fn niching_prefers_empty_niches() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+
+        // Both extremes are already taken, so the middle one must be picked
+        let selected = vec![vec![0., 1.], vec![1., 0.]];
+        let last = vec![vec![0.1, 0.9], vec![0.5, 0.5], vec![0.95, 0.05]];
+
+        let picked =
+            select_by_reference(&selected, &last, 1, &reference_directions(2, 2), &mut rng);
+
+        assert_eq!(picked, vec![1]);
+    }
+
+    #[test]
+    fn niching_infinite() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+
+        // Infinite values can't be normalized or associated, but must not panic
+        let selected = vec![vec![0., 4.], vec![4., 0.]];
+        let last = vec![
+            vec![f64::INFINITY, f64::INFINITY],
+            vec![f64::INFINITY, f64::INFINITY],
+            vec![f64::NAN, 1.],
+        ];
+
+        let picked =
+            select_by_reference(&selected, &last, 2, &reference_directions(2, 19), &mut rng);
+        assert_eq!(picked.len(), 2);
+
+        // All of them infinite
+        let picked = select_by_reference(&[], &last, 2, &reference_directions(2, 19), &mut rng);
+        assert_eq!(picked.len(), 2);
+    }
+}